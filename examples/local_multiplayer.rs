@@ -6,7 +6,6 @@ use std::f32::consts::FRAC_PI_4;
 
 use bevy::{
     color::palettes::tailwind::{BLUE_600, RED_600},
-    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
     prelude::*,
 };
 use bevy_enhanced_input::prelude::*;
@@ -31,8 +30,8 @@ impl Plugin for GamePlugin {
         app.add_input_context::<PlayerBox>()
             .add_observer(Self::apply_movement)
             .add_observer(Self::rotate)
-            .add_systems(Startup, Self::spawn)
-            .add_systems(Update, Self::update_gamepads);
+            .add_observer(Self::log_joined)
+            .add_systems(Startup, Self::spawn);
     }
 }
 
@@ -41,17 +40,21 @@ impl GamePlugin {
         commands.spawn(Camera2d);
 
         // Spawn two players with different assigned indices.
+        // Connected gamepads will be assigned to them automatically
+        // in the spawn order.
         commands.spawn((
             PlayerBox,
             Transform::from_translation(Vec3::X * 50.0),
             PlayerColor(RED_600.into()),
             Player::First,
+            GamepadSlot::default(),
         ));
         commands.spawn((
             PlayerBox,
             Transform::from_translation(-Vec3::X * 50.0),
             PlayerColor(BLUE_600.into()),
             Player::Second,
+            GamepadSlot::default(),
         ));
     }
 
@@ -66,25 +69,8 @@ impl GamePlugin {
         transform.rotate_z(FRAC_PI_4);
     }
 
-    fn update_gamepads(
-        mut commands: Commands,
-        mut connect_events: EventReader<GamepadConnectionEvent>,
-        mut gamepads: ResMut<Gamepads>,
-    ) {
-        for event in connect_events.read() {
-            match event.connection {
-                GamepadConnection::Connected { .. } => gamepads.push(event.gamepad),
-                GamepadConnection::Disconnected => {
-                    if let Some(index) = gamepads.iter().position(|&entity| entity == event.gamepad)
-                    {
-                        gamepads.swap_remove(index);
-                    }
-                }
-            }
-        }
-
-        // Trigger contexts rebuild to update associated gamepads.
-        commands.trigger(RebuildInputContexts);
+    fn log_joined(trigger: Trigger<GamepadJoined>) {
+        info!("`{}` joined `{}`", trigger.gamepad, trigger.entity());
     }
 }
 
@@ -94,10 +80,6 @@ enum Player {
     Second,
 }
 
-/// A resource that tracks all connected gamepads to pick them by index.
-#[derive(Resource, Default, Deref, DerefMut)]
-struct Gamepads(Vec<Entity>);
-
 impl InputContext for PlayerBox {
     fn context_instance(world: &World, entity: Entity) -> ContextInstance {
         let mut ctx = ContextInstance::default();
@@ -109,11 +91,7 @@ impl InputContext for PlayerBox {
 
         // By default context read inputs from all gamepads,
         // but for local multiplayer we need assign specific
-        // gamepad index.
-        let gamepads = world.resource::<Gamepads>();
-        if let Some(&entity) = gamepads.get(player as usize) {
-            ctx.set_gamepad(entity);
-        }
+        // gamepad. It's done automatically by `GamepadSlot`.
 
         // Assign different mappings based player index.
         match player {
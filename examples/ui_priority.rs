@@ -3,7 +3,7 @@
 
 mod player_box;
 
-use bevy::{color::palettes::tailwind::NEUTRAL_900, prelude::*};
+use bevy::{color::palettes::tailwind::NEUTRAL_900, prelude::*, ui::FocusPolicy};
 use bevy_egui::{egui::Window, EguiContexts, EguiPlugin};
 use bevy_enhanced_input::prelude::*;
 
@@ -63,7 +63,8 @@ impl GamePlugin {
                     ))
                     .with_children(|parent| {
                         parent.spawn((
-                            Interaction::default(), // All UI nodes with `Interaction` component will intercept all mouse input.
+                            Interaction::default(),
+                            FocusPolicy::Block, // Hovered UI nodes will intercept mouse input only with `FocusPolicy::Block`.
                             TextBundle::from_section(
                                 "Bevy UI",
                                 TextStyle {
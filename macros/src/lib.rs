@@ -11,6 +11,10 @@ struct InputActionOpts {
     accumulation: Option<Ident>,
     #[darling(default)]
     consume_input: Option<bool>,
+    #[darling(default)]
+    ignore_ui_focus: Option<bool>,
+    #[darling(default)]
+    active_in_text_input: Option<bool>,
 }
 
 #[proc_macro_derive(InputAction, attributes(input_action))]
@@ -47,6 +51,22 @@ pub fn input_action_derive(item: TokenStream) -> TokenStream {
         Default::default()
     };
 
+    let ignore_ui_focus = if let Some(ignore) = opts.ignore_ui_focus {
+        quote! {
+            const IGNORE_UI_FOCUS: bool = #ignore;
+        }
+    } else {
+        Default::default()
+    };
+
+    let active_in_text_input = if let Some(active) = opts.active_in_text_input {
+        quote! {
+            const ACTIVE_IN_TEXT_INPUT: bool = #active;
+        }
+    } else {
+        Default::default()
+    };
+
     let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
 
     TokenStream::from(quote! {
@@ -54,6 +74,8 @@ pub fn input_action_derive(item: TokenStream) -> TokenStream {
             type Output = #output;
             #accumulation
             #consume_input
+            #ignore_ui_focus
+            #active_in_text_input
         }
     })
 }
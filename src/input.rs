@@ -1,10 +1,38 @@
+pub mod gamepad_aggregation;
+pub mod gamepad_set;
+pub mod input_capture;
+pub mod input_combo;
+pub mod input_focus;
+pub mod input_prompt;
 pub(super) mod input_reader;
+pub mod input_recorder;
+pub mod input_source;
+pub mod last_input_device;
+#[cfg(feature = "picking")]
+pub mod picking;
+pub mod text_input;
+pub mod virtual_input;
 
-use std::hash::Hash;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
 
 use bevy::prelude::*;
 use bitflags::bitflags;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{
+        value::{Error as ValueError, StrDeserializer},
+        DeserializeOwned, IntoDeserializer,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::action_value::ActionValue;
+use gamepad_set::GamepadSet;
+use input_combo::{ComboButton, InputCombo};
 
 /// Inputs that can be associated with an
 /// [`InputAction`](super::input_context::input_action::InputAction).
@@ -12,30 +40,156 @@ use serde::{Deserialize, Serialize};
 /// [Input modifiers](super::input_context::input_modifier) can change the captured dimension.
 ///
 /// If the action's dimension differs from the captured input, it will be converted using
-/// [`ActionValue::convert`](crate::action_value::ActionValue::convert).
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// [`ActionValue::convert`].
+///
+/// Can be converted to and from a human-readable string via [`Display`] and [`FromStr`].
+/// Serde uses the same representation. Keyboard modifiers are written as a prefix joined by `+`
+/// (see [`ModKeys`]), followed by the input itself:
+///
+/// - [`Self::Keyboard`] as `KeyS` or `Ctrl+Shift+KeyS`.
+/// - [`Self::MouseButton`] as `Mouse:Left`, `Alt+Mouse:Right` or `Mouse:8`.
+/// - [`Self::MouseMotion`] as `MouseMotion` or `Ctrl+MouseMotion`.
+/// - [`Self::MouseWheel`] as `MouseWheel` or `Shift+MouseWheel`.
+/// - [`Self::MouseMotionDirection`] as `MouseMotion:Left` or `Ctrl+MouseMotion:Up`.
+/// - [`Self::MouseWheelDirection`] as `MouseWheel:Up` or `Shift+MouseWheel:Down`.
+/// - [`Self::GamepadButton`] as `Gamepad:South`, `LeftTrigger+Gamepad:South` or `Gamepad:21`.
+/// - [`Self::GamepadAnalog`] as `GamepadAnalog:RightTrigger2` or `LeftTrigger+GamepadAnalog:RightTrigger2`.
+/// - [`Self::GamepadAxis`] as `GamepadAxis:LeftStickX`, `RightTrigger2+GamepadAxis:LeftStickX` or `GamepadAxis:6`.
+/// - [`Self::Touch`] as `Touch:2`.
+/// - [`Self::TouchDrag`], [`Self::TouchPinch`] and [`Self::TouchPan`] by their names.
+/// - [`Self::Custom`] as `Custom:42`.
+/// - [`Self::EntityPress`] as `EntityPress:Left:1` or `EntityPress:8:1`.
+/// - [`Self::Combo`] as `Combo(KeyG, KeyH)` or `OrderedCombo(Space, Mouse:Left)`.
+///
+/// Gamepad inputs use [`GamepadModButtons`] as a prefix instead of [`ModKeys`].
+/// Keys, buttons and axes use their variant names. `Other` variants of mouse buttons,
+/// gamepad buttons and axes are written as numbers. [`KeyCode::Unidentified`] can't be parsed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// let input: Input = "Ctrl+Shift+KeyS".parse().unwrap();
+/// assert_eq!(input, KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL | ModKeys::SHIFT));
+/// assert_eq!(input.to_string(), "Ctrl+Shift+KeyS");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Input {
     /// Keyboard button, will be captured as
-    /// [`ActionValue::Bool`](crate::action_value::ActionValue::Bool).
+    /// [`ActionValue::Bool`].
     Keyboard { key: KeyCode, mod_keys: ModKeys },
     /// Mouse button, will be captured as
-    /// [`ActionValue::Bool`](crate::action_value::ActionValue::Bool).
+    /// [`ActionValue::Bool`].
     MouseButton {
         button: MouseButton,
         mod_keys: ModKeys,
     },
     /// Mouse movement, will be captured as
-    /// [`ActionValue::Axis2D`](crate::action_value::ActionValue::Axis2D).
+    /// [`ActionValue::Axis2D`].
     MouseMotion { mod_keys: ModKeys },
     /// Mouse wheel, will be captured as
-    /// [`ActionValue::Axis1D`](crate::action_value::ActionValue::Axis1D).
+    /// [`ActionValue::Axis1D`].
     MouseWheel { mod_keys: ModKeys },
+    /// Mouse movement in a single direction, will be captured as
+    /// [`ActionValue::Axis1D`].
+    ///
+    /// The value is the non-negative distance along the direction.
+    /// Movement in the opposite direction reads as zero.
+    /// Useful for binding [`ActionValue::Bool`] actions
+    /// without extra modifiers. Combine with [`Steps`](crate::input_context::input_modifier::steps::Steps)
+    /// to trigger once per distance unit.
+    MouseMotionDirection {
+        direction: MouseDirection,
+        mod_keys: ModKeys,
+    },
+    /// Mouse wheel in a single direction, will be captured as
+    /// [`ActionValue::Axis1D`].
+    ///
+    /// The value is the non-negative scroll amount along the direction.
+    /// Scrolling in the opposite direction reads as zero.
+    /// Combine with [`Steps`](crate::input_context::input_modifier::steps::Steps)
+    /// to trigger once per wheel notch instead of once per frame.
+    MouseWheelDirection {
+        direction: MouseDirection,
+        mod_keys: ModKeys,
+    },
     /// Gamepad button, will be captured as
-    /// [`ActionValue::Bool`](crate::action_value::ActionValue::Bool).
-    GamepadButton(GamepadButton),
+    /// [`ActionValue::Bool`].
+    GamepadButton {
+        button: GamepadButton,
+        mod_buttons: GamepadModButtons,
+    },
+    /// Analog value of a gamepad button, will be captured as
+    /// [`ActionValue::Axis1D`].
+    ///
+    /// Useful for triggers and pressure-sensitive buttons. The value is in range from 0.0 to 1.0
+    /// and falls back to the digital state if the gamepad doesn't report analog values.
+    ///
+    /// By default any non-zero value is considered pressed. Use
+    /// [`InputBindModCond::with_actuation`](crate::input_context::input_bind::InputBindModCond::with_actuation)
+    /// to require a specific press threshold:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// # let mut ctx = ContextInstance::default();
+    /// ctx.bind::<Accelerate>()
+    ///     .to(Input::gamepad_analog(GamepadButton::RightTrigger2));
+    /// ctx.bind::<Fire>()
+    ///     .to(Input::gamepad_analog(GamepadButton::LeftTrigger2).with_actuation(0.6));
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = f32)]
+    /// # struct Accelerate;
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Fire;
+    /// ```
+    GamepadAnalog {
+        button: GamepadButton,
+        mod_buttons: GamepadModButtons,
+    },
     /// Gamepad stick axis, will be captured as
-    /// [`ActionValue::Axis1D`](crate::action_value::ActionValue::Axis1D).
-    GamepadAxis(GamepadAxis),
+    /// [`ActionValue::Axis1D`].
+    GamepadAxis {
+        axis: GamepadAxis,
+        mod_buttons: GamepadModButtons,
+    },
+    /// Touch with the specified number of fingers, will be captured as
+    /// [`ActionValue::Bool`].
+    ///
+    /// Considered pressed only when exactly `fingers` touches are active.
+    Touch { fingers: u8 },
+    /// Single finger movement, will be captured as
+    /// [`ActionValue::Axis2D`].
+    TouchDrag,
+    /// Change in distance between two fingers, will be captured as
+    /// [`ActionValue::Axis1D`].
+    ///
+    /// Positive when fingers move apart and negative when they move closer.
+    TouchPinch,
+    /// Average movement of two fingers, will be captured as
+    /// [`ActionValue::Axis2D`].
+    TouchPan,
+    /// Input with the specified ID from a registered [`InputSource`](input_source::InputSource),
+    /// will be captured as the value returned by the source.
+    ///
+    /// Reads as [`ActionValue::Bool`] with `false`
+    /// if no source provides the ID.
+    Custom(u32),
+    /// Pointer button pressed on an entity that matches a filter, will be captured as
+    /// [`ActionValue::Bool`].
+    ///
+    /// `filter` is an ID registered via `PickingAppExt::add_entity_filter`.
+    /// Considered pressed while the button that was pressed over the entity is held.
+    /// Touches act as [`MouseButton::Left`]. The pressed entity can be obtained from
+    /// the `PressedEntities` resource.
+    ///
+    /// Requires the `picking` feature, otherwise always reads `false`.
+    EntityPress { button: MouseButton, filter: u32 },
+    /// Multiple buttons held together, will be captured as
+    /// [`ActionValue::Bool`].
+    ///
+    /// See [`InputCombo`] for details.
+    Combo(InputCombo),
 }
 
 impl Input {
@@ -55,17 +209,430 @@ impl Input {
         }
     }
 
+    /// Returns [`Input::MouseMotionDirection`] without keyboard modifiers.
+    #[must_use]
+    pub const fn mouse_motion_direction(direction: MouseDirection) -> Self {
+        Self::MouseMotionDirection {
+            direction,
+            mod_keys: ModKeys::empty(),
+        }
+    }
+
+    /// Returns [`Input::MouseWheelDirection`] without keyboard modifiers.
+    #[must_use]
+    pub const fn mouse_wheel_direction(direction: MouseDirection) -> Self {
+        Self::MouseWheelDirection {
+            direction,
+            mod_keys: ModKeys::empty(),
+        }
+    }
+
+    /// Returns [`Input::GamepadAnalog`] without gamepad modifier buttons.
+    #[must_use]
+    pub const fn gamepad_analog(button: GamepadButton) -> Self {
+        Self::GamepadAnalog {
+            button,
+            mod_buttons: GamepadModButtons::empty(),
+        }
+    }
+
+    /// Returns [`Input::EntityPress`] for [`MouseButton::Left`].
+    #[must_use]
+    pub const fn entity_press(filter: u32) -> Self {
+        Self::EntityPress {
+            button: MouseButton::Left,
+            filter,
+        }
+    }
+
+    /// Returns [`Input::Touch`] with a single finger.
+    #[must_use]
+    pub const fn touch() -> Self {
+        Self::Touch { fingers: 1 }
+    }
+
+    /// Returns a zero value in the dimension this input is captured.
+    ///
+    /// For [`Self::Custom`] returns [`ActionValue::Bool`] since the dimension is defined by the source.
+    pub(crate) fn zero_value(self) -> ActionValue {
+        match self {
+            Input::Keyboard { .. }
+            | Input::MouseButton { .. }
+            | Input::GamepadButton { .. }
+            | Input::Touch { .. }
+            | Input::Custom(_)
+            | Input::EntityPress { .. }
+            | Input::Combo(_) => false.into(),
+            Input::GamepadAxis { .. }
+            | Input::GamepadAnalog { .. }
+            | Input::MouseMotionDirection { .. }
+            | Input::MouseWheelDirection { .. }
+            | Input::TouchPinch => 0.0.into(),
+            Input::MouseMotion { .. }
+            | Input::MouseWheel { .. }
+            | Input::TouchDrag
+            | Input::TouchPan => Vec2::ZERO.into(),
+        }
+    }
+
+    /// Returns `true` if the input reads keys, including keyboard modifiers.
+    pub(crate) fn uses_keyboard(self) -> bool {
+        match self {
+            Input::Keyboard { .. } => true,
+            Input::MouseButton { mod_keys, .. }
+            | Input::MouseMotion { mod_keys }
+            | Input::MouseWheel { mod_keys }
+            | Input::MouseMotionDirection { mod_keys, .. }
+            | Input::MouseWheelDirection { mod_keys, .. } => !mod_keys.is_empty(),
+            Input::Combo(combo) => combo
+                .iter()
+                .any(|button| matches!(button, ComboButton::Keyboard(_))),
+            Input::GamepadButton { .. }
+            | Input::GamepadAnalog { .. }
+            | Input::GamepadAxis { .. }
+            | Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. } => false,
+        }
+    }
+
     /// Returns new instance without any keyboard modifiers.
     ///
     /// # Panics
     ///
-    /// Panics when called on [`Self::GamepadButton`] or [`Self::GamepadAxis`].
+    /// Panics when called on gamepad, touch or custom inputs.
     #[must_use]
     pub fn without_mod_keys(self) -> Self {
         self.with_mod_keys(ModKeys::empty())
     }
+
+    /// Returns new instance with the replaced keyboard modifiers.
+    ///
+    /// Returns [`None`] for inputs that don't support keyboard modifiers.
+    /// Non-panicking version of [`InputModKeys::with_mod_keys`].
+    #[must_use]
+    pub fn try_with_mod_keys(self, mod_keys: ModKeys) -> Option<Self> {
+        match self {
+            Input::Keyboard { key, .. } => Some(Input::Keyboard { key, mod_keys }),
+            Input::MouseButton { button, .. } => Some(Input::MouseButton { button, mod_keys }),
+            Input::MouseMotion { .. } => Some(Input::MouseMotion { mod_keys }),
+            Input::MouseWheel { .. } => Some(Input::MouseWheel { mod_keys }),
+            Input::MouseMotionDirection { direction, .. } => Some(Input::MouseMotionDirection {
+                direction,
+                mod_keys,
+            }),
+            Input::MouseWheelDirection { direction, .. } => Some(Input::MouseWheelDirection {
+                direction,
+                mod_keys,
+            }),
+            Input::GamepadButton { .. }
+            | Input::GamepadAnalog { .. }
+            | Input::GamepadAxis { .. }
+            | Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. }
+            | Input::Combo(_) => None,
+        }
+    }
+
+    /// Returns new instance with the replaced gamepad modifier buttons.
+    ///
+    /// Returns [`None`] for non-gamepad inputs.
+    /// For gamepad buttons and axes see also [`InputModButtons::with_mod_buttons`].
+    #[must_use]
+    pub fn try_with_mod_buttons(self, mod_buttons: GamepadModButtons) -> Option<Self> {
+        match self {
+            Input::GamepadButton { button, .. } => Some(Input::GamepadButton {
+                button,
+                mod_buttons,
+            }),
+            Input::GamepadAnalog { button, .. } => Some(Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            }),
+            Input::GamepadAxis { axis, .. } => Some(Input::GamepadAxis { axis, mod_buttons }),
+            Input::Keyboard { .. }
+            | Input::MouseButton { .. }
+            | Input::MouseMotion { .. }
+            | Input::MouseWheel { .. }
+            | Input::MouseMotionDirection { .. }
+            | Input::MouseWheelDirection { .. }
+            | Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. }
+            | Input::Combo(_) => None,
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Input::Keyboard { key, mod_keys } => {
+                write_mod_keys(f, mod_keys)?;
+                write!(f, "{key:?}")
+            }
+            Input::MouseButton { button, mod_keys } => {
+                write_mod_keys(f, mod_keys)?;
+                match button {
+                    MouseButton::Other(index) => write!(f, "{MOUSE_PREFIX}:{index}"),
+                    _ => write!(f, "{MOUSE_PREFIX}:{button:?}"),
+                }
+            }
+            Input::MouseMotion { mod_keys } => {
+                write_mod_keys(f, mod_keys)?;
+                write!(f, "{MOUSE_MOTION}")
+            }
+            Input::MouseWheel { mod_keys } => {
+                write_mod_keys(f, mod_keys)?;
+                write!(f, "{MOUSE_WHEEL}")
+            }
+            Input::MouseMotionDirection {
+                direction,
+                mod_keys,
+            } => {
+                write_mod_keys(f, mod_keys)?;
+                write!(f, "{MOUSE_MOTION}:{direction:?}")
+            }
+            Input::MouseWheelDirection {
+                direction,
+                mod_keys,
+            } => {
+                write_mod_keys(f, mod_keys)?;
+                write!(f, "{MOUSE_WHEEL}:{direction:?}")
+            }
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            } => {
+                write_mod_buttons(f, mod_buttons)?;
+                match button {
+                    GamepadButton::Other(index) => write!(f, "{GAMEPAD_PREFIX}:{index}"),
+                    _ => write!(f, "{GAMEPAD_PREFIX}:{button:?}"),
+                }
+            }
+            Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
+                write_mod_buttons(f, mod_buttons)?;
+                match button {
+                    GamepadButton::Other(index) => write!(f, "{GAMEPAD_ANALOG_PREFIX}:{index}"),
+                    _ => write!(f, "{GAMEPAD_ANALOG_PREFIX}:{button:?}"),
+                }
+            }
+            Input::GamepadAxis { axis, mod_buttons } => {
+                write_mod_buttons(f, mod_buttons)?;
+                match axis {
+                    GamepadAxis::Other(index) => write!(f, "{GAMEPAD_AXIS_PREFIX}:{index}"),
+                    _ => write!(f, "{GAMEPAD_AXIS_PREFIX}:{axis:?}"),
+                }
+            }
+            Input::Touch { fingers } => write!(f, "{TOUCH_PREFIX}:{fingers}"),
+            Input::TouchDrag => write!(f, "{TOUCH_DRAG}"),
+            Input::TouchPinch => write!(f, "{TOUCH_PINCH}"),
+            Input::TouchPan => write!(f, "{TOUCH_PAN}"),
+            Input::Custom(id) => write!(f, "{CUSTOM_PREFIX}:{id}"),
+            Input::EntityPress { button, filter } => match button {
+                MouseButton::Other(index) => write!(f, "{ENTITY_PRESS_PREFIX}:{index}:{filter}"),
+                _ => write!(f, "{ENTITY_PRESS_PREFIX}:{button:?}:{filter}"),
+            },
+            Input::Combo(combo) => write!(f, "{combo}"),
+        }
+    }
+}
+
+/// Writes keyboard modifiers followed by `+` if any are set.
+fn write_mod_keys(f: &mut Formatter, mod_keys: ModKeys) -> fmt::Result {
+    if mod_keys.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "{mod_keys}+")
+    }
+}
+
+/// Writes gamepad modifier buttons followed by `+` if any are set.
+fn write_mod_buttons(f: &mut Formatter, mod_buttons: GamepadModButtons) -> fmt::Result {
+    if mod_buttons.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "{mod_buttons}+")
+    }
 }
 
+impl FromStr for Input {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(combo) = InputCombo::parse(s) {
+            return combo.map(Input::Combo);
+        }
+
+        let (modifiers, name) = match s.rsplit_once('+') {
+            Some((modifiers, name)) => (Some(modifiers), name),
+            None => (None, s),
+        };
+
+        if name.is_empty() {
+            return Err(ParseInputError::Empty);
+        }
+        if modifiers == Some("") {
+            return Err(ParseInputError::EmptyModifier);
+        }
+
+        let input = match name.split_once(':') {
+            Some((MOUSE_PREFIX, value)) => {
+                parse_indexed::<MouseButton, _>(MOUSE_PREFIX, value, MouseButton::Other)?.into()
+            }
+            Some((GAMEPAD_PREFIX, value)) => {
+                parse_indexed::<GamepadButton, _>(GAMEPAD_PREFIX, value, GamepadButton::Other)?
+                    .into()
+            }
+            Some((GAMEPAD_ANALOG_PREFIX, value)) => Input::gamepad_analog(parse_indexed(
+                GAMEPAD_ANALOG_PREFIX,
+                value,
+                GamepadButton::Other,
+            )?),
+            Some((GAMEPAD_AXIS_PREFIX, value)) => {
+                parse_indexed::<GamepadAxis, _>(GAMEPAD_AXIS_PREFIX, value, GamepadAxis::Other)?
+                    .into()
+            }
+            Some((MOUSE_MOTION, value)) => {
+                Input::mouse_motion_direction(parse_direction(MOUSE_MOTION, value)?)
+            }
+            Some((MOUSE_WHEEL, value)) => {
+                Input::mouse_wheel_direction(parse_direction(MOUSE_WHEEL, value)?)
+            }
+            Some((TOUCH_PREFIX, value)) => Input::Touch {
+                fingers: parse_number(TOUCH_PREFIX, value)?,
+            },
+            Some((CUSTOM_PREFIX, value)) => Input::Custom(parse_number(CUSTOM_PREFIX, value)?),
+            Some((ENTITY_PRESS_PREFIX, value)) => {
+                let (button, filter) =
+                    value
+                        .rsplit_once(':')
+                        .ok_or_else(|| ParseInputError::InvalidValue {
+                            prefix: ENTITY_PRESS_PREFIX,
+                            value: value.to_string(),
+                        })?;
+                Input::EntityPress {
+                    button: parse_indexed(ENTITY_PRESS_PREFIX, button, MouseButton::Other)?,
+                    filter: parse_number(ENTITY_PRESS_PREFIX, filter)?,
+                }
+            }
+            Some(_) => return Err(ParseInputError::UnknownInput(name.to_string())),
+            None => match name {
+                MOUSE_MOTION => Input::mouse_motion(),
+                MOUSE_WHEEL => Input::mouse_wheel(),
+                TOUCH_DRAG => Input::TouchDrag,
+                TOUCH_PINCH => Input::TouchPinch,
+                TOUCH_PAN => Input::TouchPan,
+                _ => {
+                    let key = parse_variant::<KeyCode>(name)
+                        .ok_or_else(|| ParseInputError::UnknownInput(name.to_string()))?;
+                    key.into()
+                }
+            },
+        };
+
+        let Some(modifiers) = modifiers else {
+            return Ok(input);
+        };
+
+        let input = match input {
+            Input::GamepadButton { .. }
+            | Input::GamepadAnalog { .. }
+            | Input::GamepadAxis { .. } => input.try_with_mod_buttons(modifiers.parse()?),
+            Input::Keyboard { .. }
+            | Input::MouseButton { .. }
+            | Input::MouseMotion { .. }
+            | Input::MouseWheel { .. }
+            | Input::MouseMotionDirection { .. }
+            | Input::MouseWheelDirection { .. } => input.try_with_mod_keys(modifiers.parse()?),
+            Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. }
+            | Input::Combo(_) => None,
+        };
+
+        input.ok_or_else(|| ParseInputError::UnsupportedModKeys(name.to_string()))
+    }
+}
+
+/// Parses a unit variant by its name or an `Other` variant by its index.
+fn parse_indexed<T: DeserializeOwned, I: FromStr>(
+    prefix: &'static str,
+    value: &str,
+    other: impl FnOnce(I) -> T,
+) -> Result<T, ParseInputError> {
+    if let Ok(index) = value.parse() {
+        return Ok(other(index));
+    }
+
+    parse_variant(value).ok_or_else(|| ParseInputError::InvalidValue {
+        prefix,
+        value: value.to_string(),
+    })
+}
+
+fn parse_direction(prefix: &'static str, value: &str) -> Result<MouseDirection, ParseInputError> {
+    parse_variant(value).ok_or_else(|| ParseInputError::InvalidValue {
+        prefix,
+        value: value.to_string(),
+    })
+}
+
+fn parse_number<T: FromStr>(prefix: &'static str, value: &str) -> Result<T, ParseInputError> {
+    value.parse().map_err(|_| ParseInputError::InvalidValue {
+        prefix,
+        value: value.to_string(),
+    })
+}
+
+/// Parses a unit variant by its name using its [`Deserialize`] implementation.
+fn parse_variant<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let deserializer: StrDeserializer<ValueError> = name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+impl Serialize for Input {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Input {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+const MOUSE_PREFIX: &str = "Mouse";
+const GAMEPAD_PREFIX: &str = "Gamepad";
+const GAMEPAD_ANALOG_PREFIX: &str = "GamepadAnalog";
+const GAMEPAD_AXIS_PREFIX: &str = "GamepadAxis";
+const TOUCH_PREFIX: &str = "Touch";
+const CUSTOM_PREFIX: &str = "Custom";
+const ENTITY_PRESS_PREFIX: &str = "EntityPress";
+const MOUSE_MOTION: &str = "MouseMotion";
+const MOUSE_WHEEL: &str = "MouseWheel";
+const TOUCH_DRAG: &str = "TouchDrag";
+const TOUCH_PINCH: &str = "TouchPinch";
+const TOUCH_PAN: &str = "TouchPan";
+
 impl From<KeyCode> for Input {
     fn from(key: KeyCode) -> Self {
         Self::Keyboard {
@@ -85,14 +652,48 @@ impl From<MouseButton> for Input {
 }
 
 impl From<GamepadButton> for Input {
-    fn from(value: GamepadButton) -> Self {
-        Self::GamepadButton(value)
+    fn from(button: GamepadButton) -> Self {
+        Self::GamepadButton {
+            button,
+            mod_buttons: Default::default(),
+        }
     }
 }
 
 impl From<GamepadAxis> for Input {
-    fn from(value: GamepadAxis) -> Self {
-        Self::GamepadAxis(value)
+    fn from(axis: GamepadAxis) -> Self {
+        Self::GamepadAxis {
+            axis,
+            mod_buttons: Default::default(),
+        }
+    }
+}
+
+/// Direction for [`Input::MouseMotionDirection`] and [`Input::MouseWheelDirection`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MouseDirection {
+    /// Scrolling up or moving the mouse towards the top of the screen.
+    Up,
+    /// Scrolling down or moving the mouse towards the bottom of the screen.
+    Down,
+    /// Scrolling or moving the mouse left.
+    Left,
+    /// Scrolling or moving the mouse right.
+    Right,
+}
+
+impl MouseDirection {
+    /// Returns the non-negative amount of `delta` along this direction.
+    ///
+    /// `delta` is expected to have Y pointing up.
+    pub(crate) fn amount(self, delta: Vec2) -> f32 {
+        let amount = match self {
+            MouseDirection::Up => delta.y,
+            MouseDirection::Down => -delta.y,
+            MouseDirection::Left => -delta.x,
+            MouseDirection::Right => delta.x,
+        };
+        amount.max(0.0)
     }
 }
 
@@ -108,24 +709,78 @@ impl<I: Into<Input>> InputModKeys for I {
     ///
     /// # Panics
     ///
-    /// Panics when called on [`Input::GamepadButton`] or [`Input::GamepadAxis`].
+    /// Panics when called on gamepad, touch or custom inputs.
+    /// Use [`Input::try_with_mod_keys`] for a non-panicking version
+    /// or [`InputModButtons::with_mod_buttons`] for gamepads.
     fn with_mod_keys(self, mod_keys: ModKeys) -> Input {
-        match self.into() {
-            Input::Keyboard { key, .. } => Input::Keyboard { key, mod_keys },
-            Input::MouseButton { button, .. } => Input::MouseButton { button, mod_keys },
-            Input::MouseMotion { .. } => Input::MouseMotion { mod_keys },
-            Input::MouseWheel { .. } => Input::MouseWheel { mod_keys },
-            Input::GamepadButton { .. } | Input::GamepadAxis { .. } => {
-                panic!("keyboard modifiers can't be applied to gamepads")
-            }
+        let input = self.into();
+        match input.try_with_mod_keys(mod_keys) {
+            Some(input) => input,
+            None => match input {
+                Input::GamepadButton { .. }
+                | Input::GamepadAnalog { .. }
+                | Input::GamepadAxis { .. } => {
+                    panic!("keyboard modifiers can't be applied to gamepads")
+                }
+                Input::Custom(_) => panic!("keyboard modifiers can't be applied to custom inputs"),
+                Input::EntityPress { .. } => {
+                    panic!("keyboard modifiers can't be applied to entity presses")
+                }
+                Input::Combo(_) => panic!("keyboard modifiers can't be applied to combos"),
+                _ => panic!("keyboard modifiers can't be applied to touches"),
+            },
+        }
+    }
+}
+
+/// A trait to ergonomically assign gamepad modifier buttons to gamepad buttons and axes.
+pub trait InputModButtons {
+    /// Returns an input with assigned gamepad modifier buttons.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// # let mut ctx = ContextInstance::default();
+    /// ctx.bind::<Heal>()
+    ///     .to(GamepadButton::South.with_mod_buttons(GamepadModButtons::LEFT_TRIGGER));
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Heal;
+    /// ```
+    #[must_use]
+    fn with_mod_buttons(self, mod_buttons: GamepadModButtons) -> Input;
+}
+
+impl InputModButtons for GamepadButton {
+    fn with_mod_buttons(self, mod_buttons: GamepadModButtons) -> Input {
+        Input::GamepadButton {
+            button: self,
+            mod_buttons,
+        }
+    }
+}
+
+impl InputModButtons for GamepadAxis {
+    fn with_mod_buttons(self, mod_buttons: GamepadModButtons) -> Input {
+        Input::GamepadAxis {
+            axis: self,
+            mod_buttons,
         }
     }
 }
 
 bitflags! {
-    /// Keyboard modifiers for both left and right keys.
-    #[derive(Default, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
-    pub struct ModKeys: u8 {
+    /// Keyboard modifiers.
+    ///
+    /// Unsided modifiers like [`Self::SHIFT`] match both left and right keys,
+    /// while sided ones like [`Self::SHIFT_LEFT`] match only the specified key.
+    ///
+    /// Can be converted to and from a string like `Ctrl+ShiftLeft` via [`Display`] and [`FromStr`].
+    /// Modifiers are written as `Ctrl`, `Shift`, `Alt` and `Super` in this order, sided
+    /// modifiers have `Left` or `Right` suffix. Parsing is case-insensitive and also accepts `Control`.
+    /// Empty modifiers are an empty string.
+    #[derive(Default, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+    pub struct ModKeys: u16 {
         /// Corresponds to [`KeyCode::AltLeft`] and [`KeyCode::AltRight`].
         const ALT = 0b00000001;
         /// Corresponds to [`KeyCode::ControlLeft`] and [`KeyCode::ControlRight`].
@@ -134,26 +789,220 @@ bitflags! {
         const SHIFT = 0b00000100;
         /// Corresponds to [`KeyCode::SuperLeft`] and [`KeyCode::SuperRight`].
         const SUPER = 0b00001000;
+        /// Corresponds to [`KeyCode::AltLeft`].
+        const ALT_LEFT = 0b00010000;
+        /// Corresponds to [`KeyCode::AltRight`].
+        const ALT_RIGHT = 0b00100000;
+        /// Corresponds to [`KeyCode::ControlLeft`].
+        const CONTROL_LEFT = 0b01000000;
+        /// Corresponds to [`KeyCode::ControlRight`].
+        const CONTROL_RIGHT = 0b10000000;
+        /// Corresponds to [`KeyCode::ShiftLeft`].
+        const SHIFT_LEFT = 0b00000001_00000000;
+        /// Corresponds to [`KeyCode::ShiftRight`].
+        const SHIFT_RIGHT = 0b00000010_00000000;
+        /// Corresponds to [`KeyCode::SuperLeft`].
+        const SUPER_LEFT = 0b00000100_00000000;
+        /// Corresponds to [`KeyCode::SuperRight`].
+        const SUPER_RIGHT = 0b00001000_00000000;
     }
 }
 
 impl ModKeys {
     /// Returns an iterator over the key codes corresponding to the set modifier bits.
     ///
-    /// Each item contains left and right key codes.
-    pub fn iter_keys(self) -> impl Iterator<Item = [KeyCode; 2]> {
+    /// Each item contains left and right key codes for unsided modifiers
+    /// and a single key code for sided modifiers.
+    pub fn iter_keys(self) -> impl Iterator<Item = &'static [KeyCode]> {
         self.iter_names().map(|(_, mod_key)| match mod_key {
-            ModKeys::ALT => [KeyCode::AltLeft, KeyCode::AltRight],
-            ModKeys::CONTROL => [KeyCode::ControlLeft, KeyCode::ControlRight],
-            ModKeys::SHIFT => [KeyCode::ShiftLeft, KeyCode::ShiftRight],
-            ModKeys::SUPER => [KeyCode::SuperLeft, KeyCode::SuperRight],
+            ModKeys::ALT => &[KeyCode::AltLeft, KeyCode::AltRight][..],
+            ModKeys::CONTROL => &[KeyCode::ControlLeft, KeyCode::ControlRight],
+            ModKeys::SHIFT => &[KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            ModKeys::SUPER => &[KeyCode::SuperLeft, KeyCode::SuperRight],
+            ModKeys::ALT_LEFT => &[KeyCode::AltLeft],
+            ModKeys::ALT_RIGHT => &[KeyCode::AltRight],
+            ModKeys::CONTROL_LEFT => &[KeyCode::ControlLeft],
+            ModKeys::CONTROL_RIGHT => &[KeyCode::ControlRight],
+            ModKeys::SHIFT_LEFT => &[KeyCode::ShiftLeft],
+            ModKeys::SHIFT_RIGHT => &[KeyCode::ShiftRight],
+            ModKeys::SUPER_LEFT => &[KeyCode::SuperLeft],
+            ModKeys::SUPER_RIGHT => &[KeyCode::SuperRight],
             _ => unreachable!("iteration should yield only named flags"),
         })
     }
+
+    /// Converts sided modifiers into unsided, like [`Self::SHIFT_LEFT`] into [`Self::SHIFT`].
+    #[must_use]
+    pub fn without_sides(self) -> Self {
+        self.iter_keys()
+            .map(|keys| ModKeys::from(keys[0]))
+            .collect()
+    }
+
+    /// Converts key into a sided modifier.
+    ///
+    /// Returns [`ModKeys::empty`] if the key is not a modifier.
+    /// See also [`From<KeyCode>`] which returns unsided modifiers.
+    pub fn sided(key: KeyCode) -> Self {
+        match key {
+            KeyCode::AltLeft => ModKeys::ALT_LEFT,
+            KeyCode::AltRight => ModKeys::ALT_RIGHT,
+            KeyCode::ControlLeft => ModKeys::CONTROL_LEFT,
+            KeyCode::ControlRight => ModKeys::CONTROL_RIGHT,
+            KeyCode::ShiftLeft => ModKeys::SHIFT_LEFT,
+            KeyCode::ShiftRight => ModKeys::SHIFT_RIGHT,
+            KeyCode::SuperLeft => ModKeys::SUPER_LEFT,
+            KeyCode::SuperRight => ModKeys::SUPER_RIGHT,
+            _ => ModKeys::empty(),
+        }
+    }
+
+    /// Returns `true` if the modifier key is allowed by these modifiers either by its side or unsided.
+    fn allows(self, key: KeyCode) -> bool {
+        self.intersects(ModKeys::from(key) | ModKeys::sided(key))
+    }
+
+    /// Returns `true` if `pressed` keys contain a modifier that isn't allowed by these modifiers.
+    ///
+    /// Used for [`ModKeysMatching::Exact`].
+    pub(crate) fn has_extra<'a>(self, mut pressed: impl Iterator<Item = &'a KeyCode>) -> bool {
+        pressed.any(|&key| !ModKeys::from(key).is_empty() && !self.allows(key))
+    }
+}
+
+/// Defines how held keyboard modifiers are matched against [`ModKeys`] of an input.
+///
+/// Assigned per binding via [`InputBindModCond::with_mod_keys_matching`](crate::input_context::input_bind::InputBindModCond::with_mod_keys_matching).
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ModKeysMatching {
+    /// Input activates when the required modifiers are held, other modifiers are ignored.
+    ///
+    /// For example, `KeyS` activates when `Ctrl` is held.
+    #[default]
+    Subset,
+    /// Input activates only if no other modifiers are held.
+    ///
+    /// For example, `KeyS` doesn't activate when `Ctrl` is held
+    /// and `ShiftLeft+KeyS` doesn't activate when right `Shift` is also held.
+    Exact,
+}
+
+impl Display for ModKeys {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut separator = "";
+        for (_, name) in MOD_KEY_NAMES
+            .iter()
+            .filter(|(mod_key, _)| self.contains(*mod_key))
+        {
+            write!(f, "{separator}{name}")?;
+            separator = "+";
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for ModKeys {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mod_keys = ModKeys::empty();
+        if s.is_empty() {
+            return Ok(mod_keys);
+        }
+
+        for name in s.split('+') {
+            if name.is_empty() {
+                return Err(ParseInputError::EmptyModifier);
+            }
+            let lowercase = name.to_ascii_lowercase();
+            let lowercase = match lowercase.strip_prefix("control") {
+                Some(suffix) => format!("ctrl{suffix}"),
+                None => lowercase,
+            };
+            let (mod_key, _) = MOD_KEY_NAMES
+                .iter()
+                .find(|(_, mod_name)| mod_name.eq_ignore_ascii_case(&lowercase))
+                .ok_or_else(|| ParseInputError::UnknownModKey(name.to_string()))?;
+            mod_keys |= *mod_key;
+        }
+
+        Ok(mod_keys)
+    }
+}
+
+/// Modifier names in the display order.
+const MOD_KEY_NAMES: [(ModKeys, &str); 12] = [
+    (ModKeys::CONTROL, "Ctrl"),
+    (ModKeys::CONTROL_LEFT, "CtrlLeft"),
+    (ModKeys::CONTROL_RIGHT, "CtrlRight"),
+    (ModKeys::SHIFT, "Shift"),
+    (ModKeys::SHIFT_LEFT, "ShiftLeft"),
+    (ModKeys::SHIFT_RIGHT, "ShiftRight"),
+    (ModKeys::ALT, "Alt"),
+    (ModKeys::ALT_LEFT, "AltLeft"),
+    (ModKeys::ALT_RIGHT, "AltRight"),
+    (ModKeys::SUPER, "Super"),
+    (ModKeys::SUPER_LEFT, "SuperLeft"),
+    (ModKeys::SUPER_RIGHT, "SuperRight"),
+];
+
+/// An error returned when parsing [`Input`] or [`ModKeys`] from a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseInputError {
+    /// No input name after keyboard modifiers.
+    Empty,
+    /// Empty modifier name, like in `+KeyS` or `Ctrl++KeyS`.
+    EmptyModifier,
+    /// Keyboard modifier name is not recognized.
+    UnknownModKey(String),
+    /// Gamepad modifier button name is not recognized.
+    UnknownModButton(String),
+    /// Input name is not recognized.
+    UnknownInput(String),
+    /// Value after the prefix (like `Gamepad:`) is not valid for it.
+    InvalidValue { prefix: &'static str, value: String },
+    /// Modifiers were specified for an input that doesn't support them.
+    UnsupportedModKeys(String),
+    /// Combo contains unsupported inputs or exceeds [`InputCombo::CAPACITY`].
+    InvalidCombo(String),
+}
+
+impl Display for ParseInputError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseInputError::Empty => write!(f, "missing input name"),
+            ParseInputError::EmptyModifier => write!(f, "missing modifier name before `+`"),
+            ParseInputError::UnknownModKey(name) => write!(
+                f,
+                "unknown keyboard modifier `{name}`, expected `Ctrl`, `Shift`, `Alt` or `Super` \
+                with optional `Left` or `Right` suffix"
+            ),
+            ParseInputError::UnknownModButton(name) => write!(
+                f,
+                "unknown gamepad modifier button `{name}`, expected `LeftTrigger`, `LeftTrigger2`, \
+                `RightTrigger`, `RightTrigger2`, `LeftThumb` or `RightThumb`"
+            ),
+            ParseInputError::UnknownInput(name) => write!(f, "unknown input `{name}`"),
+            ParseInputError::InvalidValue { prefix, value } => {
+                write!(f, "`{value}` is not a valid value for `{prefix}`")
+            }
+            ParseInputError::UnsupportedModKeys(name) => {
+                write!(f, "modifiers can't be applied to `{name}`")
+            }
+            ParseInputError::InvalidCombo(combo) => write!(
+                f,
+                "`{combo}` should contain up to {} keys, mouse buttons or gamepad buttons without modifiers",
+                InputCombo::CAPACITY
+            ),
+        }
+    }
 }
 
+impl Error for ParseInputError {}
+
 impl From<KeyCode> for ModKeys {
-    /// Converts key into a named modifier
+    /// Converts key into an unsided modifier.
     ///
     /// Returns [`ModKeys::empty`] if the key is not a modifier.
     fn from(value: KeyCode) -> Self {
@@ -167,19 +1016,127 @@ impl From<KeyCode> for ModKeys {
     }
 }
 
+bitflags! {
+    /// Gamepad buttons that need to be held for a gamepad input, analogous to [`ModKeys`].
+    ///
+    /// Useful for "shift layers", like holding the left bumper to access a different set of actions.
+    /// Modifiers need to be held on the same gamepad as the input.
+    ///
+    /// Can be converted to and from a string like `LeftTrigger+RightTrigger2` via [`Display`] and [`FromStr`].
+    /// Modifiers are written as their [`GamepadButton`] variant names. Parsing is case-insensitive.
+    #[derive(Default, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+    pub struct GamepadModButtons: u8 {
+        /// Corresponds to [`GamepadButton::LeftTrigger`].
+        const LEFT_TRIGGER = 0b00000001;
+        /// Corresponds to [`GamepadButton::LeftTrigger2`].
+        const LEFT_TRIGGER2 = 0b00000010;
+        /// Corresponds to [`GamepadButton::RightTrigger`].
+        const RIGHT_TRIGGER = 0b00000100;
+        /// Corresponds to [`GamepadButton::RightTrigger2`].
+        const RIGHT_TRIGGER2 = 0b00001000;
+        /// Corresponds to [`GamepadButton::LeftThumb`].
+        const LEFT_THUMB = 0b00010000;
+        /// Corresponds to [`GamepadButton::RightThumb`].
+        const RIGHT_THUMB = 0b00100000;
+    }
+}
+
+impl GamepadModButtons {
+    /// Returns an iterator over the buttons corresponding to the set modifier bits.
+    pub fn iter_buttons(self) -> impl Iterator<Item = GamepadButton> {
+        self.iter_names().map(|(_, mod_button)| match mod_button {
+            GamepadModButtons::LEFT_TRIGGER => GamepadButton::LeftTrigger,
+            GamepadModButtons::LEFT_TRIGGER2 => GamepadButton::LeftTrigger2,
+            GamepadModButtons::RIGHT_TRIGGER => GamepadButton::RightTrigger,
+            GamepadModButtons::RIGHT_TRIGGER2 => GamepadButton::RightTrigger2,
+            GamepadModButtons::LEFT_THUMB => GamepadButton::LeftThumb,
+            GamepadModButtons::RIGHT_THUMB => GamepadButton::RightThumb,
+            _ => unreachable!("iteration should yield only named flags"),
+        })
+    }
+}
+
+impl From<GamepadButton> for GamepadModButtons {
+    /// Converts button into a named modifier
+    ///
+    /// Returns [`GamepadModButtons::empty`] if the button is not a modifier.
+    fn from(value: GamepadButton) -> Self {
+        match value {
+            GamepadButton::LeftTrigger => GamepadModButtons::LEFT_TRIGGER,
+            GamepadButton::LeftTrigger2 => GamepadModButtons::LEFT_TRIGGER2,
+            GamepadButton::RightTrigger => GamepadModButtons::RIGHT_TRIGGER,
+            GamepadButton::RightTrigger2 => GamepadModButtons::RIGHT_TRIGGER2,
+            GamepadButton::LeftThumb => GamepadModButtons::LEFT_THUMB,
+            GamepadButton::RightThumb => GamepadModButtons::RIGHT_THUMB,
+            _ => GamepadModButtons::empty(),
+        }
+    }
+}
+
+impl Display for GamepadModButtons {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut separator = "";
+        for button in self.iter_buttons() {
+            write!(f, "{separator}{button:?}")?;
+            separator = "+";
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GamepadModButtons {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mod_buttons = GamepadModButtons::empty();
+        if s.is_empty() {
+            return Ok(mod_buttons);
+        }
+
+        for name in s.split('+') {
+            if name.is_empty() {
+                return Err(ParseInputError::EmptyModifier);
+            }
+            let mod_button = match name.to_ascii_lowercase().as_str() {
+                "lefttrigger" => GamepadModButtons::LEFT_TRIGGER,
+                "lefttrigger2" => GamepadModButtons::LEFT_TRIGGER2,
+                "righttrigger" => GamepadModButtons::RIGHT_TRIGGER,
+                "righttrigger2" => GamepadModButtons::RIGHT_TRIGGER2,
+                "leftthumb" => GamepadModButtons::LEFT_THUMB,
+                "rightthumb" => GamepadModButtons::RIGHT_THUMB,
+                _ => return Err(ParseInputError::UnknownModButton(name.to_string())),
+            };
+            mod_buttons |= mod_button;
+        }
+
+        Ok(mod_buttons)
+    }
+}
+
 /// Associated gamepad.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, Hash, PartialEq, Eq)]
 pub enum GamepadDevice {
     /// Matches input from any gamepad.
     ///
-    /// For an axis, the [`ActionValue`] will be calculated as the sum of inputs from all gamepads.
+    /// For axes and analog buttons, the [`ActionValue`] will be calculated from all gamepads
+    /// according to the [`GamepadAggregation`] resource, which sums them by default.
     /// For a button, the [`ActionValue`] will be `true` if any gamepad has this button pressed.
     ///
     /// [`ActionValue`]: crate::action_value::ActionValue
+    /// [`GamepadAggregation`]: gamepad_aggregation::GamepadAggregation
     #[default]
     Any,
     /// Matches input from specific gamepad.
     Single(Entity),
+    /// Matches input from any gamepad in the set.
+    ///
+    /// Values are combined like for [`Self::Any`].
+    Set(GamepadSet),
+    /// Matches input from any gamepad except the ones in the set.
+    ///
+    /// Values are combined like for [`Self::Any`].
+    Except(GamepadSet),
 }
 
 impl GamepadDevice {
@@ -188,6 +1145,8 @@ impl GamepadDevice {
         match self {
             GamepadDevice::Any => true,
             GamepadDevice::Single(entity) => entity == gamepad_entity,
+            GamepadDevice::Set(set) => set.contains(gamepad_entity),
+            GamepadDevice::Except(set) => !set.contains(gamepad_entity),
         }
     }
 }
@@ -197,3 +1156,203 @@ impl From<Entity> for GamepadDevice {
         Self::Single(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_round_trip() {
+        let inputs = [
+            KeyCode::KeyS.into(),
+            KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL | ModKeys::SHIFT),
+            MouseButton::Left.with_mod_keys(ModKeys::ALT),
+            MouseButton::Other(8).into(),
+            Input::mouse_motion().with_mod_keys(ModKeys::SUPER),
+            Input::mouse_wheel(),
+            Input::mouse_motion_direction(MouseDirection::Left),
+            Input::mouse_wheel_direction(MouseDirection::Up).with_mod_keys(ModKeys::SHIFT),
+            GamepadButton::South.into(),
+            GamepadButton::South.with_mod_buttons(GamepadModButtons::LEFT_TRIGGER),
+            GamepadButton::Other(21).into(),
+            Input::gamepad_analog(GamepadButton::RightTrigger2),
+            Input::gamepad_analog(GamepadButton::Other(3))
+                .try_with_mod_buttons(GamepadModButtons::LEFT_TRIGGER)
+                .unwrap(),
+            GamepadAxis::LeftStickX.into(),
+            GamepadAxis::RightStickY.with_mod_buttons(
+                GamepadModButtons::RIGHT_TRIGGER | GamepadModButtons::RIGHT_TRIGGER2,
+            ),
+            GamepadAxis::Other(6).into(),
+            Input::Touch { fingers: 2 },
+            Input::TouchDrag,
+            Input::TouchPinch,
+            Input::TouchPan,
+            Input::Custom(42),
+            Input::entity_press(1),
+            Input::EntityPress {
+                button: MouseButton::Other(8),
+                filter: 2,
+            },
+        ];
+
+        for input in inputs {
+            let string = input.to_string();
+            assert_eq!(string.parse::<Input>(), Ok(input), "`{string}`");
+        }
+    }
+
+    #[test]
+    fn input_format() {
+        assert_eq!(
+            KeyCode::KeyS
+                .with_mod_keys(ModKeys::all().without_sides())
+                .to_string(),
+            "Ctrl+Shift+Alt+Super+KeyS"
+        );
+        assert_eq!(
+            Input::from(GamepadButton::South).to_string(),
+            "Gamepad:South"
+        );
+        assert_eq!(Input::mouse_wheel().to_string(), "MouseWheel");
+        assert_eq!(Input::from(MouseButton::Right).to_string(), "Mouse:Right");
+    }
+
+    #[test]
+    fn input_parse_errors() {
+        assert_eq!("".parse::<Input>(), Err(ParseInputError::Empty));
+        assert_eq!("Ctrl+".parse::<Input>(), Err(ParseInputError::Empty));
+        assert_eq!(
+            "+KeyS".parse::<Input>(),
+            Err(ParseInputError::EmptyModifier)
+        );
+        assert_eq!(
+            "Ctrl++KeyS".parse::<Input>(),
+            Err(ParseInputError::EmptyModifier)
+        );
+        assert_eq!(
+            "+Gamepad:South".parse::<Input>(),
+            Err(ParseInputError::EmptyModifier)
+        );
+        assert_eq!(
+            "Hyper+Gamepad:South".parse::<Input>(),
+            Err(ParseInputError::UnknownModButton("Hyper".to_string()))
+        );
+        assert_eq!(
+            "Hyper+KeyS".parse::<Input>(),
+            Err(ParseInputError::UnknownModKey("Hyper".to_string()))
+        );
+        assert_eq!(
+            "Key".parse::<Input>(),
+            Err(ParseInputError::UnknownInput("Key".to_string()))
+        );
+        assert_eq!(
+            "Joystick:South".parse::<Input>(),
+            Err(ParseInputError::UnknownInput("Joystick:South".to_string()))
+        );
+        assert_eq!(
+            "Gamepad:Up".parse::<Input>(),
+            Err(ParseInputError::InvalidValue {
+                prefix: "Gamepad",
+                value: "Up".to_string()
+            })
+        );
+        assert_eq!(
+            "MouseWheel:Forward".parse::<Input>(),
+            Err(ParseInputError::InvalidValue {
+                prefix: "MouseWheel",
+                value: "Forward".to_string()
+            })
+        );
+        assert_eq!(
+            "Touch:-1".parse::<Input>(),
+            Err(ParseInputError::InvalidValue {
+                prefix: "Touch",
+                value: "-1".to_string()
+            })
+        );
+        assert_eq!(
+            "Shift+Gamepad:South".parse::<Input>(),
+            Err(ParseInputError::UnknownModButton("Shift".to_string()))
+        );
+        assert_eq!(
+            "Shift+Touch:2".parse::<Input>(),
+            Err(ParseInputError::UnsupportedModKeys("Touch:2".to_string()))
+        );
+    }
+
+    #[test]
+    fn mod_keys_round_trip() {
+        for bits in 0..=ModKeys::all().bits() {
+            let mod_keys = ModKeys::from_bits_retain(bits);
+            let string = mod_keys.to_string();
+            assert_eq!(string.parse::<ModKeys>(), Ok(mod_keys), "`{string}`");
+        }
+
+        assert_eq!(
+            "control+SHIFT".parse::<ModKeys>(),
+            Ok(ModKeys::CONTROL | ModKeys::SHIFT),
+            "parsing should be case-insensitive"
+        );
+        assert_eq!(
+            "ControlLeft+shiftright".parse::<ModKeys>(),
+            Ok(ModKeys::CONTROL_LEFT | ModKeys::SHIFT_RIGHT)
+        );
+        assert_eq!(
+            (ModKeys::SHIFT_LEFT | ModKeys::CONTROL).to_string(),
+            "Ctrl+ShiftLeft"
+        );
+    }
+
+    #[test]
+    fn extra_mod_keys() {
+        let pressed = [KeyCode::ShiftLeft, KeyCode::KeyS];
+        assert!(!ModKeys::SHIFT.has_extra(pressed.iter()));
+        assert!(!ModKeys::SHIFT_LEFT.has_extra(pressed.iter()));
+        assert!(ModKeys::SHIFT_RIGHT.has_extra(pressed.iter()));
+        assert!(ModKeys::empty().has_extra(pressed.iter()));
+        assert!(!ModKeys::empty().has_extra([KeyCode::KeyS].iter()));
+    }
+
+    #[test]
+    fn mod_buttons_round_trip() {
+        for bits in 0..=GamepadModButtons::all().bits() {
+            let mod_buttons = GamepadModButtons::from_bits_retain(bits);
+            let string = mod_buttons.to_string();
+            assert_eq!(
+                string.parse::<GamepadModButtons>(),
+                Ok(mod_buttons),
+                "`{string}`"
+            );
+        }
+    }
+
+    #[test]
+    fn try_with_mod() {
+        assert_eq!(
+            Input::from(KeyCode::KeyA).try_with_mod_buttons(GamepadModButtons::LEFT_TRIGGER),
+            None
+        );
+        assert_eq!(
+            Input::from(GamepadButton::South).try_with_mod_keys(ModKeys::CONTROL),
+            None
+        );
+        assert_eq!(
+            Input::from(GamepadAxis::LeftStickX)
+                .try_with_mod_buttons(GamepadModButtons::LEFT_THUMB),
+            Some(GamepadAxis::LeftStickX.with_mod_buttons(GamepadModButtons::LEFT_THUMB))
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let input = KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL);
+        let serialized = ron::to_string(&input).unwrap();
+        assert_eq!(serialized, r#""Ctrl+KeyS""#);
+
+        let deserialized: Input = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, input);
+
+        assert!(ron::from_str::<Input>(r#""Ctrl+Gamepad:South""#).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Defines how values from multiple gamepads are combined for
+/// [`GamepadDevice::Any`](super::GamepadDevice::Any).
+///
+/// Applies to [`Input::GamepadAxis`](super::Input::GamepadAxis) and
+/// [`Input::GamepadAnalog`](super::Input::GamepadAnalog). Digital buttons
+/// are considered pressed if any gamepad has them pressed.
+///
+/// Gamepads that don't have the required [`GamepadModButtons`](super::GamepadModButtons)
+/// pressed are ignored.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(GamepadAggregation::MaxMagnitude);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GamepadAggregation {
+    /// Sum of values from all gamepads.
+    ///
+    /// The result can exceed the range of a single gamepad.
+    #[default]
+    Sum,
+    /// Value with the largest absolute value.
+    MaxMagnitude,
+    /// Value from the gamepad that was actuated last.
+    ///
+    /// A gamepad is considered actuated under the same rules as for
+    /// [`LastInputDevice`](super::last_input_device::LastInputDevice).
+    /// Falls back to [`Self::MaxMagnitude`] if no gamepad was actuated yet
+    /// or the last one is disconnected.
+    MostRecent,
+    /// Average of values from all gamepads.
+    Average,
+}
+
+impl GamepadAggregation {
+    /// Combines values from multiple gamepads.
+    ///
+    /// `last_gamepad` is the gamepad that was actuated last, used for [`Self::MostRecent`].
+    pub(crate) fn aggregate(
+        self,
+        values: impl Iterator<Item = (Entity, f32)>,
+        last_gamepad: Option<Entity>,
+    ) -> f32 {
+        match self {
+            GamepadAggregation::Sum => values.map(|(_, value)| value).sum(),
+            GamepadAggregation::MaxMagnitude => max_magnitude(values),
+            GamepadAggregation::MostRecent => {
+                let mut max = 0.0;
+                for (entity, value) in values {
+                    if Some(entity) == last_gamepad {
+                        return value;
+                    }
+                    if value.abs() > f32::abs(max) {
+                        max = value;
+                    }
+                }
+                max
+            }
+            GamepadAggregation::Average => {
+                let (sum, count) = values.fold((0.0, 0), |(sum, count), (_, value)| {
+                    (sum + value, count + 1)
+                });
+                if count == 0 {
+                    0.0
+                } else {
+                    sum / count as f32
+                }
+            }
+        }
+    }
+}
+
+fn max_magnitude(values: impl Iterator<Item = (Entity, f32)>) -> f32 {
+    values.map(|(_, value)| value).fold(0.0, |max, value| {
+        if value.abs() > f32::abs(max) {
+            value
+        } else {
+            max
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregation() {
+        let gamepad1 = Entity::from_raw(1);
+        let gamepad2 = Entity::from_raw(2);
+        let values = [(gamepad1, 0.5), (gamepad2, -1.0)];
+
+        assert_eq!(
+            GamepadAggregation::Sum.aggregate(values.into_iter(), None),
+            -0.5
+        );
+        assert_eq!(
+            GamepadAggregation::MaxMagnitude.aggregate(values.into_iter(), None),
+            -1.0
+        );
+        assert_eq!(
+            GamepadAggregation::MostRecent.aggregate(values.into_iter(), Some(gamepad1)),
+            0.5
+        );
+        assert_eq!(
+            GamepadAggregation::MostRecent.aggregate(values.into_iter(), None),
+            -1.0
+        );
+        assert_eq!(
+            GamepadAggregation::Average.aggregate(values.into_iter(), None),
+            -0.25
+        );
+        assert_eq!(
+            GamepadAggregation::Average.aggregate([].into_iter(), None),
+            0.0
+        );
+    }
+}
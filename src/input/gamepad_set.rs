@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::GamepadDevice;
+
+/// Multiple gamepad entities for [`GamepadDevice::Set`] and [`GamepadDevice::Except`].
+///
+/// Can hold up to [`Self::CAPACITY`] gamepads.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut world = World::new();
+/// # let gamepad1 = world.spawn(Gamepad::default()).id();
+/// # let gamepad2 = world.spawn(Gamepad::default()).id();
+/// let mut ctx = ContextInstance::default();
+///
+/// // Both gamepads drive the same character.
+/// ctx.set_gamepad(GamepadSet::default().with(gamepad1).with(gamepad2));
+///
+/// // All gamepads except the ones used by other players.
+/// ctx.set_gamepad(GamepadDevice::Except(GamepadSet::default().with(gamepad1)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct GamepadSet {
+    gamepads: [Option<Entity>; Self::CAPACITY],
+}
+
+impl GamepadSet {
+    /// Maximum number of gamepads in a set.
+    pub const CAPACITY: usize = 8;
+
+    /// Adds a gamepad to the set.
+    ///
+    /// Does nothing if the gamepad is already in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set already contains [`Self::CAPACITY`] gamepads.
+    #[must_use]
+    pub fn with(mut self, gamepad_entity: Entity) -> Self {
+        if self.contains(gamepad_entity) {
+            return self;
+        }
+
+        let slot = self
+            .gamepads
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .unwrap_or_else(|| panic!("set can't contain more than {} gamepads", Self::CAPACITY));
+        *slot = Some(gamepad_entity);
+        self
+    }
+
+    /// Removes a gamepad from the set.
+    #[must_use]
+    pub fn without(mut self, gamepad_entity: Entity) -> Self {
+        for slot in &mut self.gamepads {
+            if *slot == Some(gamepad_entity) {
+                *slot = None;
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the set contains the gamepad.
+    pub fn contains(&self, gamepad_entity: Entity) -> bool {
+        self.gamepads.contains(&Some(gamepad_entity))
+    }
+
+    /// Returns an iterator over gamepads in the set.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.gamepads.iter().flatten().copied()
+    }
+}
+
+impl From<GamepadSet> for GamepadDevice {
+    fn from(value: GamepadSet) -> Self {
+        Self::Set(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set() {
+        let gamepad1 = Entity::from_raw(1);
+        let gamepad2 = Entity::from_raw(2);
+        let set = GamepadSet::default().with(gamepad1).with(gamepad1);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [gamepad1]);
+
+        let set = set.with(gamepad2).without(gamepad1);
+        assert!(!set.contains(gamepad1));
+        assert!(set.contains(gamepad2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let mut set = GamepadSet::default();
+        for index in 0..=GamepadSet::CAPACITY as u32 {
+            set = set.with(Entity::from_raw(index));
+        }
+    }
+}
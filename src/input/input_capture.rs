@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bitflags::bitflags;
+
+use super::{GamepadDevice, Input, ModKeys};
+
+/// Captures the next actuated [`Input`].
+///
+/// Useful for rebinding screens. While capture is active, all actions are transitioned into
+/// [`ActionState::None`](crate::input_context::context_instance::ActionState::None)
+/// and not evaluated. After capture, inputs that were held will be ignored until released.
+///
+/// Keyboard modifiers that are held while pressing a key or a mouse button will be assigned
+/// to the captured input. A modifier key itself is captured on release if no other input
+/// was pressed.
+///
+/// Once the input is captured, the capture stops and [`InputCaptured`] is triggered.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn start_rebinding(mut capture: ResMut<InputCapture>) {
+///     capture.start(
+///         CaptureSettings::default()
+///             .with_devices(CaptureDevices::KEYBOARD | CaptureDevices::MOUSE)
+///             .with_cancel(KeyCode::Escape),
+///     );
+/// }
+///
+/// fn apply_binding(trigger: Trigger<InputCaptured>) {
+///     if let Some(input) = trigger.input {
+///         info!("captured `{input:?}`");
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct InputCapture(Option<CaptureSettings>);
+
+impl InputCapture {
+    /// Starts capturing with the specified settings.
+    ///
+    /// Replaces the settings if capture is already active.
+    pub fn start(&mut self, settings: CaptureSettings) {
+        debug!("starting input capture with `{settings:?}`");
+        self.0 = Some(settings);
+    }
+
+    /// Stops capturing without triggering [`InputCaptured`].
+    pub fn stop(&mut self) {
+        self.0 = None;
+    }
+
+    /// Returns `true` if capture is active.
+    pub fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Returns current settings if capture is active.
+    pub fn settings(&self) -> Option<&CaptureSettings> {
+        self.0.as_ref()
+    }
+}
+
+/// Settings for [`InputCapture`].
+#[derive(Clone, Debug)]
+pub struct CaptureSettings {
+    /// Devices from which inputs can be captured.
+    ///
+    /// By default all devices are allowed.
+    pub devices: CaptureDevices,
+
+    /// Gamepad from which inputs can be captured.
+    ///
+    /// By default it's [`GamepadDevice::Any`].
+    pub gamepad: GamepadDevice,
+
+    /// Inputs that will never be captured.
+    pub excluded: Vec<Input>,
+
+    /// Inputs that stop the capture without a result.
+    ///
+    /// Held keyboard modifiers are ignored, so pressing `Escape` while holding `Shift`
+    /// also matches `Escape`.
+    pub cancel: Vec<Input>,
+
+    /// Trigger threshold for axes.
+    ///
+    /// By default it's 0.5.
+    pub actuation: f32,
+}
+
+impl CaptureSettings {
+    /// Sets devices from which inputs can be captured.
+    #[must_use]
+    pub fn with_devices(mut self, devices: CaptureDevices) -> Self {
+        self.devices = devices;
+        self
+    }
+
+    /// Sets gamepad from which inputs can be captured.
+    #[must_use]
+    pub fn with_gamepad(mut self, gamepad: impl Into<GamepadDevice>) -> Self {
+        self.gamepad = gamepad.into();
+        self
+    }
+
+    /// Adds an input that will never be captured.
+    #[must_use]
+    pub fn with_excluded(mut self, input: impl Into<Input>) -> Self {
+        self.excluded.push(input.into());
+        self
+    }
+
+    /// Adds an input that stops the capture without a result.
+    #[must_use]
+    pub fn with_cancel(mut self, input: impl Into<Input>) -> Self {
+        self.cancel.push(input.into());
+        self
+    }
+
+    /// Sets trigger threshold for axes.
+    #[must_use]
+    pub fn with_actuation(mut self, actuation: f32) -> Self {
+        self.actuation = actuation;
+        self
+    }
+
+    /// Returns `true` if the captured input matches one of [`Self::cancel`].
+    pub(crate) fn is_cancel(&self, input: Input) -> bool {
+        let without_mod_keys = input.try_with_mod_keys(ModKeys::empty());
+        self.cancel
+            .iter()
+            .any(|&cancel| cancel == input || Some(cancel) == without_mod_keys)
+    }
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            devices: CaptureDevices::all(),
+            gamepad: Default::default(),
+            excluded: Default::default(),
+            cancel: Default::default(),
+            actuation: 0.5,
+        }
+    }
+}
+
+bitflags! {
+    /// Devices from which [`InputCapture`] can capture inputs.
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct CaptureDevices: u8 {
+        /// Keyboard keys.
+        const KEYBOARD = 0b00000001;
+        /// Mouse buttons and wheel.
+        const MOUSE = 0b00000010;
+        /// Gamepad buttons and axes.
+        const GAMEPAD = 0b00000100;
+    }
+}
+
+/// Triggers when [`InputCapture`] finishes.
+#[derive(Debug, Event, Clone, Copy)]
+pub struct InputCaptured {
+    /// Captured input.
+    ///
+    /// [`None`] if the capture was canceled by one of [`CaptureSettings::cancel`].
+    pub input: Option<Input>,
+}
@@ -0,0 +1,282 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::prelude::*;
+
+use super::{Input, ParseInputError};
+
+/// Multiple buttons that need to be held together, will be captured as
+/// [`ActionValue::Bool`](crate::action_value::ActionValue::Bool).
+///
+/// Unlike [`ModKeys`](super::ModKeys), can contain any keys, mouse buttons or gamepad buttons.
+/// Treated as a single [`Input`]: consuming it consumes all its buttons.
+/// Gamepad buttons are read from the gamepad associated with the context.
+///
+/// Can hold up to [`Self::CAPACITY`] buttons. Empty combos are never pressed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut ctx = ContextInstance::default();
+/// ctx.bind::<Inspect>()
+///     .to(InputCombo::default().with(KeyCode::KeyG).with(KeyCode::KeyH));
+/// ctx.bind::<Dash>().to(InputCombo::default()
+///     .with(KeyCode::Space)
+///     .with(MouseButton::Left)
+///     .ordered());
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct Inspect;
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct Dash;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InputCombo {
+    buttons: [Option<ComboButton>; Self::CAPACITY],
+    ordered: bool,
+}
+
+impl InputCombo {
+    /// Maximum number of buttons in a combo.
+    pub const CAPACITY: usize = 4;
+
+    /// Adds a button to the combo.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combo already contains [`Self::CAPACITY`] buttons.
+    #[must_use]
+    pub fn with(mut self, button: impl Into<ComboButton>) -> Self {
+        let slot = self
+            .buttons
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .unwrap_or_else(|| panic!("combo can't contain more than {} buttons", Self::CAPACITY));
+        *slot = Some(button.into());
+        self
+    }
+
+    /// Requires buttons to be pressed in the order they were added.
+    ///
+    /// Buttons pressed during the same frame are considered pressed in order.
+    #[must_use]
+    pub fn ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    /// Returns `true` if buttons need to be pressed in order.
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Returns `true` if the combo contains no buttons.
+    pub fn is_empty(&self) -> bool {
+        self.buttons[0].is_none()
+    }
+
+    /// Returns an iterator over buttons in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = ComboButton> + '_ {
+        self.buttons.iter().flatten().copied()
+    }
+}
+
+impl From<InputCombo> for Input {
+    fn from(value: InputCombo) -> Self {
+        Self::Combo(value)
+    }
+}
+
+impl Display for InputCombo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let prefix = if self.ordered {
+            ORDERED_COMBO_PREFIX
+        } else {
+            COMBO_PREFIX
+        };
+        write!(f, "{prefix}(")?;
+        let mut separator = "";
+        for button in self.iter() {
+            write!(f, "{separator}{}", Input::from(button))?;
+            separator = ", ";
+        }
+        write!(f, ")")
+    }
+}
+
+impl InputCombo {
+    /// Parses a combo like `Combo(KeyG, KeyH)` or `OrderedCombo(Space, Mouse:Left)`.
+    ///
+    /// Returns [`None`] if the string is not a combo.
+    pub(super) fn parse(s: &str) -> Option<Result<Self, ParseInputError>> {
+        let (ordered, buttons) = if let Some(buttons) = s.strip_prefix(ORDERED_COMBO_PREFIX) {
+            (true, buttons)
+        } else {
+            (false, s.strip_prefix(COMBO_PREFIX)?)
+        };
+        let buttons = buttons.strip_prefix('(')?.strip_suffix(')')?;
+        if buttons.trim().is_empty() {
+            return Some(Err(ParseInputError::InvalidCombo(s.to_string())));
+        }
+
+        let mut combo = InputCombo {
+            ordered,
+            ..Default::default()
+        };
+        for (index, name) in buttons.split(',').map(str::trim).enumerate() {
+            let button = match name.parse::<Input>() {
+                Ok(input) => ComboButton::try_from(input),
+                Err(e) => return Some(Err(e)),
+            };
+            let Ok(button) = button else {
+                return Some(Err(ParseInputError::InvalidCombo(s.to_string())));
+            };
+            let Some(slot) = combo.buttons.get_mut(index) else {
+                return Some(Err(ParseInputError::InvalidCombo(s.to_string())));
+            };
+            *slot = Some(button);
+        }
+
+        Some(Ok(combo))
+    }
+}
+
+const COMBO_PREFIX: &str = "Combo";
+const ORDERED_COMBO_PREFIX: &str = "OrderedCombo";
+
+/// Button that can be a part of [`InputCombo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComboButton {
+    /// Keyboard key.
+    Keyboard(KeyCode),
+    /// Mouse button.
+    MouseButton(MouseButton),
+    /// Gamepad button.
+    GamepadButton(GamepadButton),
+}
+
+impl From<KeyCode> for ComboButton {
+    fn from(value: KeyCode) -> Self {
+        Self::Keyboard(value)
+    }
+}
+
+impl From<MouseButton> for ComboButton {
+    fn from(value: MouseButton) -> Self {
+        Self::MouseButton(value)
+    }
+}
+
+impl From<GamepadButton> for ComboButton {
+    fn from(value: GamepadButton) -> Self {
+        Self::GamepadButton(value)
+    }
+}
+
+impl From<ComboButton> for Input {
+    fn from(value: ComboButton) -> Self {
+        match value {
+            ComboButton::Keyboard(key) => key.into(),
+            ComboButton::MouseButton(button) => button.into(),
+            ComboButton::GamepadButton(button) => button.into(),
+        }
+    }
+}
+
+impl TryFrom<Input> for ComboButton {
+    type Error = Input;
+
+    /// Converts keyboard keys, mouse buttons and gamepad buttons without modifiers.
+    ///
+    /// Returns the input back for other inputs.
+    fn try_from(value: Input) -> Result<Self, Self::Error> {
+        match value {
+            Input::Keyboard { key, mod_keys } if mod_keys.is_empty() => Ok(key.into()),
+            Input::MouseButton { button, mod_keys } if mod_keys.is_empty() => Ok(button.into()),
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            } if mod_buttons.is_empty() => Ok(button.into()),
+            _ => Err(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        let combo = InputCombo::default()
+            .with(KeyCode::KeyG)
+            .with(MouseButton::Left)
+            .with(GamepadButton::South);
+        assert_eq!(
+            combo.iter().collect::<Vec<_>>(),
+            [
+                KeyCode::KeyG.into(),
+                MouseButton::Left.into(),
+                GamepadButton::South.into()
+            ]
+        );
+        assert!(!combo.is_ordered());
+        assert!(!combo.is_empty());
+        assert!(InputCombo::default().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = InputCombo::default()
+            .with(KeyCode::KeyA)
+            .with(KeyCode::KeyB)
+            .with(KeyCode::KeyC)
+            .with(KeyCode::KeyD)
+            .with(KeyCode::KeyE);
+    }
+
+    #[test]
+    fn round_trip() {
+        let combos = [
+            InputCombo::default()
+                .with(KeyCode::KeyG)
+                .with(KeyCode::KeyH),
+            InputCombo::default()
+                .with(KeyCode::Space)
+                .with(MouseButton::Left)
+                .with(GamepadButton::Other(3))
+                .ordered(),
+        ];
+
+        for combo in combos {
+            let input = Input::from(combo);
+            let string = input.to_string();
+            assert_eq!(string.parse::<Input>(), Ok(input), "`{string}`");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "Combo(Ctrl+KeyG)".parse::<Input>(),
+            Err(ParseInputError::InvalidCombo(
+                "Combo(Ctrl+KeyG)".to_string()
+            ))
+        );
+        assert_eq!(
+            "Combo(KeyA, KeyB, KeyC, KeyD, KeyE)".parse::<Input>(),
+            Err(ParseInputError::InvalidCombo(
+                "Combo(KeyA, KeyB, KeyC, KeyD, KeyE)".to_string()
+            ))
+        );
+        assert_eq!(
+            "Combo()".parse::<Input>(),
+            Err(ParseInputError::InvalidCombo("Combo()".to_string()))
+        );
+        assert_eq!(
+            "Combo(KeyA, Foo)".parse::<Input>(),
+            Err(ParseInputError::UnknownInput("Foo".to_string()))
+        );
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(feature = "picking")]
+use bevy::picking::{focus::HoverMap, PickingBehavior};
+use bevy::prelude::*;
+#[cfg(feature = "egui_priority")]
+use bevy_egui::EguiContext;
+
+/// Tracks which UI layer owns the keyboard and the pointer.
+///
+/// While the keyboard is owned by UI, keyboard inputs and [`ModKeys`](super::ModKeys)
+/// read zero. While the pointer is owned by UI, mouse and touch inputs read zero.
+/// Contexts and actions can opt out with
+/// [`InputContext::IGNORE_UI_FOCUS`](crate::input_context::InputContext::IGNORE_UI_FOCUS)
+/// and [`InputAction::IGNORE_UI_FOCUS`](crate::input_context::input_action::InputAction::IGNORE_UI_FOCUS).
+///
+/// The focus is updated automatically each frame:
+///
+/// * If the `ui_priority` feature is enabled, the pointer is owned by a hovered or pressed
+///   node with `FocusPolicy::Block`. `Button` uses it by default, but other nodes default to
+///   `FocusPolicy::Pass`, so insert `FocusPolicy::Block` for panels that should capture the pointer.
+/// * If the `picking` feature is enabled, the pointer is owned by a UI node hovered according to
+///   `HoverMap` that blocks lower entities according to its `PickingBehavior`.
+///   Takes precedence over `ui_priority`.
+/// * If the `egui_priority` feature is enabled, egui owns the keyboard or the pointer
+///   whenever it wants the corresponding input.
+///
+/// Bevy UI doesn't track keyboard focus, so it should be assigned manually via
+/// [`Self::set_keyboard_focus`], for example when a text field is selected.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn select_text_field(trigger: Trigger<TextFieldSelected>, mut focus: ResMut<InputFocus>) {
+///     focus.set_keyboard_focus(Some(trigger.entity()));
+/// }
+///
+/// fn deselect_text_field(_trigger: Trigger<TextFieldDeselected>, mut focus: ResMut<InputFocus>) {
+///     focus.set_keyboard_focus(None);
+/// }
+/// # #[derive(Event)]
+/// # struct TextFieldSelected;
+/// # #[derive(Event)]
+/// # struct TextFieldDeselected;
+/// ```
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct InputFocus {
+    keyboard: FocusOwner,
+    pointer: FocusOwner,
+    keyboard_node: Option<Entity>,
+}
+
+impl InputFocus {
+    /// Returns the current owner of the keyboard.
+    pub fn keyboard(&self) -> FocusOwner {
+        self.keyboard
+    }
+
+    /// Returns the current owner of the pointer.
+    pub fn pointer(&self) -> FocusOwner {
+        self.pointer
+    }
+
+    /// Assigns keyboard focus to a UI node or returns it to the world with [`None`].
+    ///
+    /// Applied on the next update. Egui still takes priority if it wants keyboard input.
+    pub fn set_keyboard_focus(&mut self, node: Option<Entity>) {
+        self.keyboard_node = node;
+    }
+
+    pub(crate) fn update(
+        mut focus: ResMut<Self>,
+        #[cfg(all(feature = "ui_priority", not(feature = "picking")))] nodes: Query<(
+            Entity,
+            &Interaction,
+            Option<&bevy::ui::FocusPolicy>,
+        )>,
+        #[cfg(feature = "picking")] hover_map: Option<Res<HoverMap>>,
+        #[cfg(feature = "picking")] picking_nodes: Query<
+            Option<&PickingBehavior>,
+            With<bevy::ui::Node>,
+        >,
+        // In egui mutable reference is required to get contexts,
+        // unless `immutable_ctx` feature is enabled.
+        #[cfg(feature = "egui_priority")] mut egui: Query<&mut EguiContext>,
+    ) {
+        #[cfg(feature = "picking")]
+        let ui_pointer = hover_map
+            .iter()
+            .flat_map(|hover_map| hover_map.values())
+            .flat_map(|hovered| hovered.keys())
+            .copied()
+            .find(|&entity| {
+                picking_nodes.get(entity).is_ok_and(|behavior| {
+                    behavior.is_none_or(|behavior| behavior.should_block_lower)
+                })
+            });
+        #[cfg(all(feature = "ui_priority", not(feature = "picking")))]
+        let ui_pointer = nodes
+            .iter()
+            .find(|&(_, &interaction, policy)| {
+                interaction != Interaction::None
+                    && policy.is_none_or(|&policy| policy == bevy::ui::FocusPolicy::Block)
+            })
+            .map(|(entity, ..)| entity);
+        #[cfg(not(any(feature = "ui_priority", feature = "picking")))]
+        let ui_pointer = None;
+
+        #[cfg(feature = "egui_priority")]
+        let (egui_keyboard, egui_pointer) =
+            egui.iter_mut()
+                .fold((false, false), |(keyboard, pointer), mut ctx| {
+                    let ctx = ctx.get_mut();
+                    (
+                        keyboard || ctx.wants_keyboard_input(),
+                        pointer || ctx.wants_pointer_input(),
+                    )
+                });
+        #[cfg(not(feature = "egui_priority"))]
+        let (egui_keyboard, egui_pointer) = (false, false);
+
+        let keyboard = if egui_keyboard {
+            FocusOwner::Egui
+        } else {
+            focus.keyboard_node.map(FocusOwner::Ui).unwrap_or_default()
+        };
+        let pointer = if egui_pointer {
+            FocusOwner::Egui
+        } else {
+            ui_pointer.map(FocusOwner::Ui).unwrap_or_default()
+        };
+
+        if focus.keyboard != keyboard {
+            debug!("changing keyboard focus to `{keyboard:?}`");
+            focus.keyboard = keyboard;
+        }
+        if focus.pointer != pointer {
+            debug!("changing pointer focus to `{pointer:?}`");
+            focus.pointer = pointer;
+        }
+    }
+}
+
+/// Owner of an input device for [`InputFocus`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FocusOwner {
+    /// Input is available for contexts.
+    #[default]
+    World,
+    /// Input is owned by a Bevy UI node.
+    Ui(Entity),
+    /// Input is owned by egui.
+    Egui,
+}
@@ -0,0 +1,432 @@
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{GamepadModButtons, Input, ModKeys};
+
+impl Input {
+    /// Returns data for displaying this input in on-screen prompts.
+    ///
+    /// `family` is used only for gamepad inputs to pick the controller-specific labels and glyphs.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// let input = Input::from(GamepadButton::South);
+    /// assert_eq!(input.prompt(GamepadFamily::Xbox).label, "A");
+    /// assert_eq!(input.prompt(GamepadFamily::PlayStation).glyph, "playstation_cross");
+    /// ```
+    pub fn prompt(self, family: GamepadFamily) -> InputPrompt {
+        match self {
+            Input::Keyboard { key, mod_keys } => InputPrompt {
+                mod_keys,
+                mod_buttons: GamepadModButtons::empty(),
+                label: key_label(key),
+                glyph: format!("keyboard_{}", snake_case(&key_name(key))).into(),
+            },
+            Input::MouseButton { button, mod_keys } => {
+                let (label, glyph) = match button {
+                    MouseButton::Left => ("Mouse Left".into(), "mouse_left".into()),
+                    MouseButton::Right => ("Mouse Right".into(), "mouse_right".into()),
+                    MouseButton::Middle => ("Mouse Middle".into(), "mouse_middle".into()),
+                    MouseButton::Back => ("Mouse Back".into(), "mouse_back".into()),
+                    MouseButton::Forward => ("Mouse Forward".into(), "mouse_forward".into()),
+                    MouseButton::Other(index) => (
+                        format!("Mouse Button {index}").into(),
+                        format!("mouse_{index}").into(),
+                    ),
+                };
+                InputPrompt {
+                    mod_keys,
+                    mod_buttons: GamepadModButtons::empty(),
+                    label,
+                    glyph,
+                }
+            }
+            Input::MouseMotion { mod_keys } => InputPrompt {
+                mod_keys,
+                mod_buttons: GamepadModButtons::empty(),
+                label: "Mouse Move".into(),
+                glyph: "mouse_motion".into(),
+            },
+            Input::MouseWheel { mod_keys } => InputPrompt {
+                mod_keys,
+                mod_buttons: GamepadModButtons::empty(),
+                label: "Mouse Wheel".into(),
+                glyph: "mouse_wheel".into(),
+            },
+            Input::MouseMotionDirection {
+                direction,
+                mod_keys,
+            } => InputPrompt {
+                mod_keys,
+                mod_buttons: GamepadModButtons::empty(),
+                label: format!("Mouse Move {direction:?}").into(),
+                glyph: format!("mouse_motion_{}", snake_case(&format!("{direction:?}"))).into(),
+            },
+            Input::MouseWheelDirection {
+                direction,
+                mod_keys,
+            } => InputPrompt {
+                mod_keys,
+                mod_buttons: GamepadModButtons::empty(),
+                label: format!("Mouse Wheel {direction:?}").into(),
+                glyph: format!("mouse_wheel_{}", snake_case(&format!("{direction:?}"))).into(),
+            },
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            }
+            | Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
+                let label = button_label(button, family);
+                InputPrompt {
+                    mod_keys: ModKeys::empty(),
+                    mod_buttons,
+                    glyph: format!("{}_{}", family.glyph_prefix(), snake_case(&label)).into(),
+                    label,
+                }
+            }
+            Input::GamepadAxis { axis, mod_buttons } => {
+                let label: Cow<_> = match axis {
+                    GamepadAxis::LeftStickX => "Left Stick X".into(),
+                    GamepadAxis::LeftStickY => "Left Stick Y".into(),
+                    GamepadAxis::LeftZ => "Left Z".into(),
+                    GamepadAxis::RightStickX => "Right Stick X".into(),
+                    GamepadAxis::RightStickY => "Right Stick Y".into(),
+                    GamepadAxis::RightZ => "Right Z".into(),
+                    GamepadAxis::Other(index) => format!("Axis {index}").into(),
+                };
+                InputPrompt {
+                    mod_keys: ModKeys::empty(),
+                    mod_buttons,
+                    glyph: format!("{}_{}", family.glyph_prefix(), snake_case(&label)).into(),
+                    label,
+                }
+            }
+            Input::Touch { fingers } => InputPrompt {
+                mod_keys: ModKeys::empty(),
+                mod_buttons: GamepadModButtons::empty(),
+                label: match fingers {
+                    1 => "Tap".into(),
+                    _ => format!("{fingers}-Finger Tap").into(),
+                },
+                glyph: format!("touch_{fingers}").into(),
+            },
+            Input::TouchDrag => InputPrompt {
+                mod_keys: ModKeys::empty(),
+                mod_buttons: GamepadModButtons::empty(),
+                label: "Drag".into(),
+                glyph: "touch_drag".into(),
+            },
+            Input::TouchPinch => InputPrompt {
+                mod_keys: ModKeys::empty(),
+                mod_buttons: GamepadModButtons::empty(),
+                label: "Pinch".into(),
+                glyph: "touch_pinch".into(),
+            },
+            Input::TouchPan => InputPrompt {
+                mod_keys: ModKeys::empty(),
+                mod_buttons: GamepadModButtons::empty(),
+                label: "Two-Finger Drag".into(),
+                glyph: "touch_pan".into(),
+            },
+            Input::Custom(id) => InputPrompt {
+                mod_keys: ModKeys::empty(),
+                mod_buttons: GamepadModButtons::empty(),
+                label: format!("Custom {id}").into(),
+                glyph: format!("custom_{id}").into(),
+            },
+            Input::EntityPress { button, .. } => Input::from(button).prompt(family),
+            Input::Combo(combo) => {
+                let prompts: Vec<_> = combo
+                    .iter()
+                    .map(|button| Input::from(button).prompt(family))
+                    .collect();
+                InputPrompt {
+                    mod_keys: ModKeys::empty(),
+                    mod_buttons: GamepadModButtons::empty(),
+                    label: prompts
+                        .iter()
+                        .map(|prompt| &*prompt.label)
+                        .collect::<Vec<_>>()
+                        .join(" + ")
+                        .into(),
+                    glyph: prompts
+                        .iter()
+                        .map(|prompt| &*prompt.glyph)
+                        .collect::<Vec<_>>()
+                        .join("+")
+                        .into(),
+                }
+            }
+        }
+    }
+}
+
+/// Data for displaying an [`Input`] in on-screen prompts.
+///
+/// Returned by [`Input::prompt`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InputPrompt {
+    /// Keyboard modifiers that should be displayed before the input.
+    ///
+    /// Can be formatted using [`ModKeys`]'s [`Display`](std::fmt::Display) implementation.
+    pub mod_keys: ModKeys,
+
+    /// Gamepad modifier buttons that should be displayed before the input.
+    ///
+    /// Prompts for each button can be obtained via [`GamepadModButtons::iter_buttons`].
+    pub mod_buttons: GamepadModButtons,
+
+    /// Human-readable name in English, like `A` for Xbox or `Cross` for PlayStation.
+    ///
+    /// Suitable as a fallback when no localization is available.
+    pub label: Cow<'static, str>,
+
+    /// Stable identifier in `snake_case` prefixed with the device, like `xbox_a` or `keyboard_space`.
+    ///
+    /// Can be used as a key for icon lookup or localization.
+    pub glyph: Cow<'static, str>,
+}
+
+/// Controller family that determines gamepad button labels and glyphs in [`InputPrompt`].
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GamepadFamily {
+    /// Layout-neutral names like `South` or `L1`.
+    #[default]
+    Generic,
+    /// Microsoft Xbox controllers.
+    Xbox,
+    /// Sony PlayStation controllers.
+    PlayStation,
+    /// Nintendo Switch controllers.
+    Nintendo,
+}
+
+impl GamepadFamily {
+    /// Detects the family by gamepad's USB vendor ID.
+    ///
+    /// Returns [`Self::Generic`] if the vendor is unknown or not available.
+    pub fn from_gamepad(gamepad: &Gamepad) -> Self {
+        match gamepad.vendor_id() {
+            Some(0x045e) => Self::Xbox,
+            Some(0x054c) => Self::PlayStation,
+            Some(0x057e) => Self::Nintendo,
+            _ => Self::Generic,
+        }
+    }
+
+    fn glyph_prefix(self) -> &'static str {
+        match self {
+            GamepadFamily::Generic => "gamepad",
+            GamepadFamily::Xbox => "xbox",
+            GamepadFamily::PlayStation => "playstation",
+            GamepadFamily::Nintendo => "nintendo",
+        }
+    }
+}
+
+fn button_label(button: GamepadButton, family: GamepadFamily) -> Cow<'static, str> {
+    use GamepadFamily::*;
+
+    let label = match (button, family) {
+        (GamepadButton::South, Generic) => "South",
+        (GamepadButton::South, Xbox) => "A",
+        (GamepadButton::South, PlayStation) => "Cross",
+        (GamepadButton::South, Nintendo) => "B",
+        (GamepadButton::East, Generic) => "East",
+        (GamepadButton::East, Xbox) => "B",
+        (GamepadButton::East, PlayStation) => "Circle",
+        (GamepadButton::East, Nintendo) => "A",
+        (GamepadButton::North, Generic) => "North",
+        (GamepadButton::North, Xbox) => "Y",
+        (GamepadButton::North, PlayStation) => "Triangle",
+        (GamepadButton::North, Nintendo) => "X",
+        (GamepadButton::West, Generic) => "West",
+        (GamepadButton::West, Xbox) => "X",
+        (GamepadButton::West, PlayStation) => "Square",
+        (GamepadButton::West, Nintendo) => "Y",
+        (GamepadButton::C, _) => "C",
+        (GamepadButton::Z, _) => "Z",
+        (GamepadButton::LeftTrigger, Xbox) => "LB",
+        (GamepadButton::LeftTrigger, Nintendo) => "L",
+        (GamepadButton::LeftTrigger, Generic | PlayStation) => "L1",
+        (GamepadButton::LeftTrigger2, Xbox) => "LT",
+        (GamepadButton::LeftTrigger2, Nintendo) => "ZL",
+        (GamepadButton::LeftTrigger2, Generic | PlayStation) => "L2",
+        (GamepadButton::RightTrigger, Xbox) => "RB",
+        (GamepadButton::RightTrigger, Nintendo) => "R",
+        (GamepadButton::RightTrigger, Generic | PlayStation) => "R1",
+        (GamepadButton::RightTrigger2, Xbox) => "RT",
+        (GamepadButton::RightTrigger2, Nintendo) => "ZR",
+        (GamepadButton::RightTrigger2, Generic | PlayStation) => "R2",
+        (GamepadButton::Select, Generic) => "Select",
+        (GamepadButton::Select, Xbox) => "View",
+        (GamepadButton::Select, PlayStation) => "Share",
+        (GamepadButton::Select, Nintendo) => "Minus",
+        (GamepadButton::Start, Generic) => "Start",
+        (GamepadButton::Start, Xbox) => "Menu",
+        (GamepadButton::Start, PlayStation) => "Options",
+        (GamepadButton::Start, Nintendo) => "Plus",
+        (GamepadButton::Mode, Generic) => "Mode",
+        (GamepadButton::Mode, Xbox) => "Xbox",
+        (GamepadButton::Mode, PlayStation) => "PS",
+        (GamepadButton::Mode, Nintendo) => "Home",
+        (GamepadButton::LeftThumb, Xbox | Nintendo) => "LS",
+        (GamepadButton::LeftThumb, Generic | PlayStation) => "L3",
+        (GamepadButton::RightThumb, Xbox | Nintendo) => "RS",
+        (GamepadButton::RightThumb, Generic | PlayStation) => "R3",
+        (GamepadButton::DPadUp, _) => "D-Pad Up",
+        (GamepadButton::DPadDown, _) => "D-Pad Down",
+        (GamepadButton::DPadLeft, _) => "D-Pad Left",
+        (GamepadButton::DPadRight, _) => "D-Pad Right",
+        (GamepadButton::Other(index), _) => return format!("Button {index}").into(),
+    };
+
+    label.into()
+}
+
+fn key_label(key: KeyCode) -> Cow<'static, str> {
+    let label = match key {
+        KeyCode::ControlLeft => "Left Ctrl",
+        KeyCode::ControlRight => "Right Ctrl",
+        KeyCode::ShiftLeft => "Left Shift",
+        KeyCode::ShiftRight => "Right Shift",
+        KeyCode::AltLeft => "Left Alt",
+        KeyCode::AltRight => "Right Alt",
+        KeyCode::SuperLeft => "Left Super",
+        KeyCode::SuperRight => "Right Super",
+        KeyCode::Escape => "Esc",
+        KeyCode::Backquote => "`",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        _ => return split_words(&key_name(key)).join(" ").into(),
+    };
+
+    label.into()
+}
+
+/// Returns key's variant name without `Key` and `Digit` prefixes for letters and digits.
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    match name
+        .strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+    {
+        Some(stripped) if stripped.len() == 1 => stripped.to_string(),
+        _ => name,
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    split_words(name)
+        .iter()
+        .map(|word| {
+            word.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Splits `PascalCase` or space-separated name into words.
+fn split_words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in name.split(' ') {
+        let mut start = 0;
+        let mut prev_lowercase = false;
+        for (index, c) in part.char_indices() {
+            if c.is_ascii_uppercase() && prev_lowercase {
+                words.push(&part[start..index]);
+                start = index;
+            }
+            prev_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+        if start < part.len() {
+            words.push(&part[start..]);
+        }
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{input_combo::InputCombo, InputModKeys};
+
+    #[test]
+    fn keyboard() {
+        let prompt = KeyCode::KeyS
+            .with_mod_keys(ModKeys::CONTROL)
+            .prompt(GamepadFamily::Xbox);
+        assert_eq!(prompt.mod_keys, ModKeys::CONTROL);
+        assert_eq!(prompt.label, "S");
+        assert_eq!(prompt.glyph, "keyboard_s");
+
+        let prompt = Input::from(KeyCode::ArrowUp).prompt(GamepadFamily::Generic);
+        assert_eq!(prompt.label, "Arrow Up");
+        assert_eq!(prompt.glyph, "keyboard_arrow_up");
+
+        let prompt = Input::from(KeyCode::Digit1).prompt(GamepadFamily::Generic);
+        assert_eq!(prompt.label, "1");
+        assert_eq!(prompt.glyph, "keyboard_1");
+
+        let prompt = Input::from(KeyCode::F12).prompt(GamepadFamily::Generic);
+        assert_eq!(prompt.label, "F12");
+        assert_eq!(prompt.glyph, "keyboard_f12");
+    }
+
+    #[test]
+    fn gamepad() {
+        let south = Input::from(GamepadButton::South);
+        assert_eq!(south.prompt(GamepadFamily::Xbox).label, "A");
+        assert_eq!(south.prompt(GamepadFamily::PlayStation).label, "Cross");
+        assert_eq!(south.prompt(GamepadFamily::Nintendo).label, "B");
+        assert_eq!(south.prompt(GamepadFamily::Generic).label, "South");
+        assert_eq!(south.prompt(GamepadFamily::Xbox).glyph, "xbox_a");
+
+        let prompt = Input::from(GamepadButton::DPadUp).prompt(GamepadFamily::Nintendo);
+        assert_eq!(prompt.label, "D-Pad Up");
+        assert_eq!(prompt.glyph, "nintendo_dpad_up");
+
+        let prompt = Input::from(GamepadAxis::LeftStickX).prompt(GamepadFamily::PlayStation);
+        assert_eq!(prompt.label, "Left Stick X");
+        assert_eq!(prompt.glyph, "playstation_left_stick_x");
+    }
+
+    #[test]
+    fn other() {
+        let prompt = MouseButton::Other(8).with_mod_keys(ModKeys::SHIFT);
+        let prompt = prompt.prompt(GamepadFamily::Generic);
+        assert_eq!(prompt.mod_keys, ModKeys::SHIFT);
+        assert_eq!(prompt.label, "Mouse Button 8");
+        assert_eq!(prompt.glyph, "mouse_8");
+
+        let prompt = Input::Touch { fingers: 2 }.prompt(GamepadFamily::Generic);
+        assert_eq!(prompt.label, "2-Finger Tap");
+        assert_eq!(prompt.glyph, "touch_2");
+
+        let combo = InputCombo::default()
+            .with(KeyCode::KeyG)
+            .with(GamepadButton::South);
+        let prompt = Input::from(combo).prompt(GamepadFamily::Xbox);
+        assert_eq!(prompt.label, "G + A");
+        assert_eq!(prompt.glyph, "keyboard_g+xbox_a");
+    }
+}
@@ -2,14 +2,26 @@ use std::hash::Hash;
 
 use bevy::{
     ecs::system::SystemParam,
-    input::mouse::{MouseMotion, MouseWheel},
+    input::{
+        gamepad,
+        mouse::{MouseMotion, MouseWheel},
+    },
     prelude::*,
-    utils::HashSet,
+    utils::{HashMap, HashSet},
 };
-#[cfg(feature = "egui_priority")]
-use bevy_egui::EguiContext;
 
-use super::{GamepadDevice, Input, ModKeys};
+use super::{
+    gamepad_aggregation::GamepadAggregation,
+    input_capture::{CaptureDevices, CaptureSettings},
+    input_combo::{ComboButton, InputCombo},
+    input_focus::{FocusOwner, InputFocus},
+    input_recorder::InputRecorder,
+    input_source::InputSources,
+    last_input_device::InputDevice,
+    text_input::TextInputMode,
+    virtual_input::VirtualInput,
+    GamepadDevice, GamepadModButtons, Input, ModKeys,
+};
 use crate::action_value::ActionValue;
 
 /// Reads input from multiple sources.
@@ -19,50 +31,36 @@ pub(crate) struct InputReader<'w, 's> {
     mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
     mouse_motion_events: EventReader<'w, 's, MouseMotion>,
     mouse_wheel_events: EventReader<'w, 's, MouseWheel>,
-    gamepads: Query<'w, 's, &'static Gamepad>,
+    gamepads: Query<'w, 's, (Entity, &'static Gamepad)>,
+    touches: Res<'w, Touches>,
+    virtual_input: Res<'w, VirtualInput>,
+    sources: Res<'w, InputSources>,
+    #[cfg(feature = "picking")]
+    pressed_entities: Option<Res<'w, super::picking::PressedEntities>>,
+    focus: Res<'w, InputFocus>,
+    text_input: Res<'w, TextInputMode>,
+    aggregation: Res<'w, GamepadAggregation>,
     consumed: Local<'s, ConsumedInput>,
     gamepad_device: Local<'s, GamepadDevice>,
+    ignore_ui_focus: Local<'s, bool>,
+    active_in_text_input: Local<'s, bool>,
+    blocked: Local<'s, bool>,
     mouse_wheel: Local<'s, Vec2>,
     mouse_motion: Local<'s, Vec2>,
-    #[cfg(feature = "ui_priority")]
-    interactions: Query<'w, 's, &'static Interaction>,
-    // In egui mutable reference is required to get contexts,
-    // unless `immutable_ctx` feature is enabled.
-    #[cfg(feature = "egui_priority")]
-    egui: Query<'w, 's, &'static mut EguiContext>,
+    touch: Local<'s, TouchState>,
+    actuated_devices: Local<'s, Vec<InputDevice>>,
+    last_gamepad: Local<'s, Option<Entity>>,
+    press_frames: Local<'s, PressFrames>,
+    recorder: ResMut<'w, InputRecorder>,
 }
 
 impl InputReader<'_, '_> {
-    /// Resets all consumed values and reads mouse events.
+    /// Resets all consumed values and reads mouse, touch and virtual input.
     pub(crate) fn update_state(&mut self) {
         self.consumed.reset();
-
-        #[cfg(feature = "ui_priority")]
-        if self
-            .interactions
-            .iter()
-            .any(|&interaction| interaction != Interaction::None)
-        {
-            self.consumed.ui_wants_mouse = true;
-        }
-
-        #[cfg(feature = "egui_priority")]
-        if self
-            .egui
-            .iter_mut()
-            .any(|mut ctx| ctx.get_mut().wants_keyboard_input())
-        {
-            self.consumed.ui_wants_keyboard = true;
-        }
-
-        #[cfg(feature = "egui_priority")]
-        if self
-            .egui
-            .iter_mut()
-            .any(|mut ctx| ctx.get_mut().wants_pointer_input())
-        {
-            self.consumed.ui_wants_mouse = true;
-        }
+        self.consumed.ui_wants_keyboard = self.focus.keyboard() != FocusOwner::World;
+        self.consumed.ui_wants_mouse = self.focus.pointer() != FocusOwner::World;
+        self.consumed.text_input = self.text_input.is_active();
 
         // Mouse motion and wheel input need to be accumulated
         // because they only exist as events, and subsequent reads
@@ -77,6 +75,91 @@ impl InputReader<'_, '_> {
             .read()
             .map(|event| Vec2::new(event.x, event.y))
             .sum();
+
+        self.touch.update(&self.touches);
+        self.update_press_frames();
+    }
+
+    /// Remembers when buttons were pressed to check [`InputCombo::is_ordered`].
+    fn update_press_frames(&mut self) {
+        let press_frames = &mut *self.press_frames;
+        press_frames.frame += 1;
+        let frame = press_frames.frame;
+
+        for &key in self.keys.get_just_pressed() {
+            press_frames.buttons.insert((key.into(), None), frame);
+        }
+        for &button in self.mouse_buttons.get_just_pressed() {
+            press_frames.buttons.insert((button.into(), None), frame);
+        }
+        for (entity, gamepad) in &self.gamepads {
+            for &button in gamepad.get_just_pressed() {
+                press_frames
+                    .buttons
+                    .insert((button.into(), Some(entity)), frame);
+            }
+        }
+    }
+
+    /// Collects physical devices that were used during this frame.
+    ///
+    /// Gamepad axes are considered used only if their absolute value is at least `actuation`.
+    pub(crate) fn update_actuated_devices(&mut self, actuation: f32) {
+        self.actuated_devices.clear();
+
+        if self.keys.get_just_pressed().next().is_some() {
+            self.actuated_devices.push(InputDevice::Keyboard);
+        }
+
+        if self.mouse_buttons.get_just_pressed().next().is_some()
+            || *self.mouse_motion != Vec2::ZERO
+            || *self.mouse_wheel != Vec2::ZERO
+        {
+            self.actuated_devices.push(InputDevice::Mouse);
+        }
+
+        if self.touches.iter_just_pressed().next().is_some()
+            || self.touch.drag != Vec2::ZERO
+            || self.touch.pan != Vec2::ZERO
+            || self.touch.pinch != 0.0
+        {
+            self.actuated_devices.push(InputDevice::Touch);
+        }
+
+        for (entity, gamepad) in &self.gamepads {
+            let axis_actuated = gamepad
+                .analog()
+                .all_axes_and_values()
+                .any(|(input, value)| {
+                    matches!(input, gamepad::GamepadInput::Axis(_)) && value.abs() >= actuation
+                });
+            if axis_actuated || gamepad.get_just_pressed().next().is_some() {
+                self.actuated_devices.push(InputDevice::Gamepad(entity));
+            }
+        }
+
+        // Keep the previous gamepad while it's still in use.
+        let last_actuated = self.last_gamepad.is_some_and(|entity| {
+            self.actuated_devices
+                .contains(&InputDevice::Gamepad(entity))
+        });
+        if !last_actuated {
+            if let Some(entity) = self
+                .actuated_devices
+                .iter()
+                .find_map(|&device| match device {
+                    InputDevice::Gamepad(entity) => Some(entity),
+                    _ => None,
+                })
+            {
+                *self.last_gamepad = Some(entity);
+            }
+        }
+    }
+
+    /// Returns devices collected by [`Self::update_actuated_devices`].
+    pub(crate) fn actuated_devices(&self) -> &[InputDevice] {
+        &self.actuated_devices
     }
 
     /// Assigns a gamepad from which [`Self::value`] should read input.
@@ -84,103 +167,322 @@ impl InputReader<'_, '_> {
         *self.gamepad_device = gamepad.into();
     }
 
+    /// Sets whether [`Self::value`] should read input owned by UI.
+    pub(crate) fn set_ignore_ui_focus(&mut self, ignore_ui_focus: bool) {
+        *self.ignore_ui_focus = ignore_ui_focus;
+    }
+
+    /// Sets whether [`Self::value`] should read keyboard while [`TextInputMode`] is active.
+    pub(crate) fn set_active_in_text_input(&mut self, active_in_text_input: bool) {
+        *self.active_in_text_input = active_in_text_input;
+    }
+
+    /// Sets whether [`Self::value`] should read all inputs as consumed.
+    ///
+    /// Used to block contexts under a blocking layer.
+    pub(crate) fn set_blocked(&mut self, blocked: bool) {
+        *self.blocked = blocked;
+    }
+
     /// Returns the [`ActionValue`] for the given [`Input`] if exists.
     ///
+    /// Falls back to the value from [`VirtualInput`] if the device value is zero.
+    ///
     /// See also [`Self::consume`] and [`Self::set_gamepad`].
-    pub(crate) fn value(&self, input: impl Into<Input>) -> ActionValue {
-        match input.into() {
-            Input::Keyboard { key, mod_keys } => {
-                let pressed = !self.consumed.ui_wants_keyboard
-                    && self.keys.pressed(key)
-                    && !self.consumed.keys.contains(&key)
-                    && self.mod_keys_pressed(mod_keys);
+    pub(crate) fn value(&mut self, input: impl Into<Input>) -> ActionValue {
+        let input = input.into();
+        let gamepad = *self.gamepad_device;
+        let value = match self.recorder.playback_value(gamepad, input) {
+            Some(value) => value,
+            None => {
+                let value = self.device_value(input);
+                self.recorder.record(gamepad, input, value);
+                value
+            }
+        };
+
+        if self.is_consumed(input) {
+            return ActionValue::zero(value.dim());
+        }
 
+        value
+    }
+
+    /// Returns the value from the device or injected by [`VirtualInput`].
+    ///
+    /// Doesn't take consumption into account.
+    fn device_value(&self, input: Input) -> ActionValue {
+        let value = match input {
+            Input::Keyboard { key, mod_keys } => {
+                let pressed = self.keys.pressed(key) && self.mod_keys_pressed(mod_keys);
                 pressed.into()
             }
             Input::MouseButton { button, mod_keys } => {
-                let pressed = !self.consumed.ui_wants_mouse
-                    && self.mouse_buttons.pressed(button)
-                    && !self.consumed.mouse_buttons.contains(&button)
-                    && self.mod_keys_pressed(mod_keys);
-
+                let pressed = self.mouse_buttons.pressed(button) && self.mod_keys_pressed(mod_keys);
                 pressed.into()
             }
             Input::MouseMotion { mod_keys } => {
-                if self.consumed.ui_wants_mouse || !self.mod_keys_pressed(mod_keys) {
-                    return Vec2::ZERO.into();
-                }
-
-                let value = *self.mouse_motion;
+                let value = if self.mod_keys_pressed(mod_keys) {
+                    *self.mouse_motion
+                } else {
+                    Vec2::ZERO
+                };
                 value.into()
             }
             Input::MouseWheel { mod_keys } => {
-                if self.consumed.ui_wants_mouse || !self.mod_keys_pressed(mod_keys) {
-                    return Vec2::ZERO.into();
-                }
-
-                let value = *self.mouse_wheel;
+                let value = if self.mod_keys_pressed(mod_keys) {
+                    *self.mouse_wheel
+                } else {
+                    Vec2::ZERO
+                };
                 value.into()
             }
-            Input::GamepadButton(button) => {
-                let input = GamepadInput {
-                    gamepad: *self.gamepad_device,
-                    input: button,
+            Input::MouseMotionDirection {
+                direction,
+                mod_keys,
+            } => {
+                let value = if self.mod_keys_pressed(mod_keys) {
+                    // Motion uses screen coordinates with Y pointing down.
+                    let motion = *self.mouse_motion * Vec2::new(1.0, -1.0);
+                    direction.amount(motion)
+                } else {
+                    0.0
                 };
-
-                if self.consumed.gamepad_buttons.contains(&input) {
-                    return false.into();
-                }
-
+                value.into()
+            }
+            Input::MouseWheelDirection {
+                direction,
+                mod_keys,
+            } => {
+                let value = if self.mod_keys_pressed(mod_keys) {
+                    direction.amount(*self.mouse_wheel)
+                } else {
+                    0.0
+                };
+                value.into()
+            }
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            } => {
                 let pressed = match *self.gamepad_device {
-                    GamepadDevice::Any => {
-                        self.gamepads.iter().any(|gamepad| gamepad.pressed(button))
+                    GamepadDevice::Single(entity) => {
+                        self.gamepads.get(entity).is_ok_and(|(_, gamepad)| {
+                            gamepad.pressed(button) && mod_buttons_pressed(gamepad, mod_buttons)
+                        })
                     }
-                    GamepadDevice::Single(entity) => self
-                        .gamepads
-                        .get(entity)
-                        .is_ok_and(|gamepad| gamepad.pressed(button)),
+                    _ => self.gamepads.iter().any(|(entity, gamepad)| {
+                        self.gamepad_device.matches(entity)
+                            && gamepad.pressed(button)
+                            && mod_buttons_pressed(gamepad, mod_buttons)
+                    }),
                 };
 
                 pressed.into()
             }
-            Input::GamepadAxis(axis) => {
-                let input = GamepadInput {
-                    gamepad: *self.gamepad_device,
-                    input: axis,
+            Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
+                let value = match *self.gamepad_device {
+                    GamepadDevice::Single(entity) => self
+                        .gamepads
+                        .get(entity)
+                        .ok()
+                        .filter(|(_, gamepad)| mod_buttons_pressed(gamepad, mod_buttons))
+                        .map(|(_, gamepad)| analog_value(gamepad, button))
+                        .unwrap_or_default(),
+                    _ => self.aggregation.aggregate(
+                        self.matching_gamepads(mod_buttons)
+                            .map(|(entity, gamepad)| (entity, analog_value(gamepad, button))),
+                        *self.last_gamepad,
+                    ),
                 };
 
-                if self.consumed.gamepad_axes.contains(&input) {
-                    return 0.0.into();
-                }
-
+                value.into()
+            }
+            Input::GamepadAxis { axis, mod_buttons } => {
                 let value = match *self.gamepad_device {
-                    GamepadDevice::Any => self.gamepads.iter().find_map(|gamepad| {
-                        gamepad.get_unclamped(axis).filter(|&value| value != 0.0)
-                    }),
                     GamepadDevice::Single(entity) => self
                         .gamepads
                         .get(entity)
                         .ok()
-                        .and_then(|gamepad| gamepad.get(axis)),
+                        .filter(|(_, gamepad)| mod_buttons_pressed(gamepad, mod_buttons))
+                        .and_then(|(_, gamepad)| gamepad.get(axis))
+                        .unwrap_or_default(),
+                    _ => self.aggregation.aggregate(
+                        self.matching_gamepads(mod_buttons)
+                            .map(|(entity, gamepad)| {
+                                (entity, gamepad.get_unclamped(axis).unwrap_or_default())
+                            }),
+                        *self.last_gamepad,
+                    ),
                 };
 
-                let value = value.unwrap_or_default();
                 value.into()
             }
+            Input::Touch { fingers } => {
+                let pressed = self.touch.fingers == fingers;
+                pressed.into()
+            }
+            Input::TouchDrag => self.touch.drag.into(),
+            Input::TouchPinch => self.touch.pinch.into(),
+            Input::TouchPan => self.touch.pan.into(),
+            Input::Custom(id) => self.sources.value(id).unwrap_or(false.into()),
+            #[cfg(feature = "picking")]
+            Input::EntityPress { button, filter } => self
+                .pressed_entities
+                .as_ref()
+                .is_some_and(|pressed| pressed.is_pressed(button, filter))
+                .into(),
+            #[cfg(not(feature = "picking"))]
+            Input::EntityPress { .. } => false.into(),
+            Input::Combo(combo) => {
+                let pressed = if has_gamepad_buttons(combo) {
+                    self.gamepads
+                        .iter()
+                        .filter(|&(entity, _)| self.gamepad_device.matches(entity))
+                        .any(|gamepad| self.combo_pressed(combo, Some(gamepad)))
+                } else {
+                    self.combo_pressed(combo, None)
+                };
+
+                pressed.into()
+            }
+        };
+
+        if value.as_bool() {
+            return value;
         }
+
+        self.virtual_input
+            .get(
+                *self.gamepad_device,
+                input,
+                value.dim(),
+                *self.aggregation,
+                *self.last_gamepad,
+            )
+            .unwrap_or(value)
     }
 
-    fn mod_keys_pressed(&self, mod_keys: ModKeys) -> bool {
-        if !mod_keys.is_empty() && self.consumed.ui_wants_keyboard {
+    /// Returns the gamepad that provides the value for the input.
+    ///
+    /// Returns [`None`] for non-gamepad inputs or if no gamepad provides the value.
+    pub(crate) fn gamepad_entity(&self, input: Input) -> Option<Entity> {
+        let is_active = |entity, gamepad: &Gamepad| match input {
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            } => gamepad.pressed(button) && mod_buttons_pressed(gamepad, mod_buttons),
+            Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => analog_value(gamepad, button) != 0.0 && mod_buttons_pressed(gamepad, mod_buttons),
+            Input::GamepadAxis { axis, mod_buttons } => {
+                gamepad.get(axis).is_some_and(|value| value != 0.0)
+                    && mod_buttons_pressed(gamepad, mod_buttons)
+            }
+            Input::Combo(combo) => {
+                has_gamepad_buttons(combo) && self.combo_pressed(combo, Some((entity, gamepad)))
+            }
+            _ => false,
+        };
+
+        self.gamepads
+            .iter()
+            .find(|&(entity, gamepad)| {
+                self.gamepad_device.matches(entity) && is_active(entity, gamepad)
+            })
+            .map(|(entity, _)| entity)
+    }
+
+    /// Returns gamepads that match the assigned device and have the modifier buttons pressed.
+    fn matching_gamepads(
+        &self,
+        mod_buttons: GamepadModButtons,
+    ) -> impl Iterator<Item = (Entity, &Gamepad)> {
+        self.gamepads.iter().filter(move |&(entity, gamepad)| {
+            self.gamepad_device.matches(entity) && mod_buttons_pressed(gamepad, mod_buttons)
+        })
+    }
+
+    /// Returns `true` if all combo buttons are pressed, in order if required.
+    ///
+    /// Gamepad buttons are read from the specified gamepad.
+    /// Empty combos are never pressed.
+    fn combo_pressed(&self, combo: InputCombo, gamepad: Option<(Entity, &Gamepad)>) -> bool {
+        if combo.is_empty() {
             return false;
         }
 
-        if self.consumed.mod_keys.intersects(mod_keys) {
-            return false;
+        let mut last_frame = 0;
+        for button in combo.iter() {
+            let (pressed, entity) = match button {
+                ComboButton::Keyboard(key) => (self.keys.pressed(key), None),
+                ComboButton::MouseButton(button) => (self.mouse_buttons.pressed(button), None),
+                ComboButton::GamepadButton(button) => match gamepad {
+                    Some((entity, gamepad)) => (gamepad.pressed(button), Some(entity)),
+                    None => (false, None),
+                },
+            };
+            if !pressed {
+                return false;
+            }
+
+            if combo.is_ordered() {
+                // Buttons that were held before tracking started are considered pressed first.
+                let frame = self
+                    .press_frames
+                    .buttons
+                    .get(&(button, entity))
+                    .copied()
+                    .unwrap_or_default();
+                if frame < last_frame {
+                    return false;
+                }
+                last_frame = frame;
+            }
         }
 
+        true
+    }
+
+    /// Returns `true` if keyboard modifiers that aren't required by the input are held.
+    ///
+    /// Modifier keys that are part of the input itself are considered required.
+    pub(crate) fn extra_mod_keys_pressed(&self, input: Input) -> bool {
+        let required = match input {
+            Input::Keyboard { key, mod_keys } => mod_keys | ModKeys::sided(key),
+            Input::MouseButton { mod_keys, .. }
+            | Input::MouseMotion { mod_keys }
+            | Input::MouseWheel { mod_keys }
+            | Input::MouseMotionDirection { mod_keys, .. }
+            | Input::MouseWheelDirection { mod_keys, .. } => mod_keys,
+            Input::Combo(combo) => combo
+                .iter()
+                .filter_map(|button| match button {
+                    ComboButton::Keyboard(key) => Some(ModKeys::sided(key)),
+                    _ => None,
+                })
+                .collect(),
+            Input::GamepadButton { .. }
+            | Input::GamepadAnalog { .. }
+            | Input::GamepadAxis { .. }
+            | Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. } => return false,
+        };
+
+        required.has_extra(self.keys.get_pressed())
+    }
+
+    fn mod_keys_pressed(&self, mod_keys: ModKeys) -> bool {
         for keys in mod_keys.iter_keys() {
-            if !self.keys.any_pressed(keys) {
+            if !self.keys.any_pressed(keys.iter().copied()) {
                 return false;
             }
         }
@@ -188,6 +490,101 @@ impl InputReader<'_, '_> {
         true
     }
 
+    /// Returns `true` if the input was consumed or captured by UI.
+    fn is_consumed(&self, input: Input) -> bool {
+        if *self.blocked {
+            return true;
+        }
+
+        match input {
+            Input::Keyboard { key, mod_keys } => {
+                self.keyboard_blocked()
+                    || self.consumed.keys.contains(&key)
+                    || self.mod_keys_consumed(mod_keys)
+            }
+            Input::MouseButton { button, mod_keys } => {
+                self.ui_wants_mouse()
+                    || self.consumed.mouse_buttons.contains(&button)
+                    || self.mod_keys_consumed(mod_keys)
+            }
+            Input::MouseMotion { mod_keys } | Input::MouseMotionDirection { mod_keys, .. } => {
+                self.ui_wants_mouse()
+                    || self.consumed.mouse_motion
+                    || self.mod_keys_consumed(mod_keys)
+            }
+            Input::MouseWheel { mod_keys } | Input::MouseWheelDirection { mod_keys, .. } => {
+                self.ui_wants_mouse()
+                    || self.consumed.mouse_wheel
+                    || self.mod_keys_consumed(mod_keys)
+            }
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            }
+            | Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
+                let input = GamepadInput {
+                    gamepad: *self.gamepad_device,
+                    input: button,
+                };
+
+                self.consumed.gamepad_buttons.contains(&input)
+                    || self.mod_buttons_consumed(mod_buttons)
+            }
+            Input::GamepadAxis { axis, mod_buttons } => {
+                let input = GamepadInput {
+                    gamepad: *self.gamepad_device,
+                    input: axis,
+                };
+
+                self.consumed.gamepad_axes.contains(&input)
+                    || self.mod_buttons_consumed(mod_buttons)
+            }
+            Input::Touch { .. } => self.ui_wants_mouse() || self.consumed.touch,
+            Input::TouchDrag => self.ui_wants_mouse() || self.consumed.touch_drag,
+            Input::TouchPinch => self.ui_wants_mouse() || self.consumed.touch_pinch,
+            Input::TouchPan => self.ui_wants_mouse() || self.consumed.touch_pan,
+            Input::Custom(id) => self.consumed.custom.contains(&id),
+            Input::EntityPress { button, filter } => {
+                self.ui_wants_mouse() || self.consumed.entity_presses.contains(&(button, filter))
+            }
+            Input::Combo(combo) => combo.iter().any(|button| self.is_consumed(button.into())),
+        }
+    }
+
+    fn ui_wants_keyboard(&self) -> bool {
+        self.consumed.ui_wants_keyboard && !*self.ignore_ui_focus
+    }
+
+    /// Returns `true` if the keyboard is owned by UI or used for text entry.
+    fn keyboard_blocked(&self) -> bool {
+        self.ui_wants_keyboard() || (self.consumed.text_input && !*self.active_in_text_input)
+    }
+
+    fn ui_wants_mouse(&self) -> bool {
+        self.consumed.ui_wants_mouse && !*self.ignore_ui_focus
+    }
+
+    fn mod_keys_consumed(&self, mod_keys: ModKeys) -> bool {
+        if !mod_keys.is_empty() && self.keyboard_blocked() {
+            return true;
+        }
+
+        self.consumed
+            .mod_keys
+            .without_sides()
+            .intersects(mod_keys.without_sides())
+    }
+
+    fn mod_buttons_consumed(&self, mod_buttons: GamepadModButtons) -> bool {
+        self.consumed
+            .gamepad_mod_buttons
+            .get(&*self.gamepad_device)
+            .is_some_and(|consumed| consumed.intersects(mod_buttons))
+    }
+
     /// Consumes the input, making it unavailable for [`Self::value`].
     ///
     /// Resets with [`Self::update_state`].
@@ -201,57 +598,267 @@ impl InputReader<'_, '_> {
                 self.consumed.mouse_buttons.insert(button);
                 self.consumed.mod_keys.insert(mod_keys);
             }
-            Input::MouseMotion { mod_keys } => {
-                *self.mouse_motion = Vec2::ZERO;
+            Input::MouseMotion { mod_keys } | Input::MouseMotionDirection { mod_keys, .. } => {
+                self.consumed.mouse_motion = true;
                 self.consumed.mod_keys.insert(mod_keys);
             }
-            Input::MouseWheel { mod_keys } => {
-                *self.mouse_wheel = Vec2::ZERO;
+            Input::MouseWheel { mod_keys } | Input::MouseWheelDirection { mod_keys, .. } => {
+                self.consumed.mouse_wheel = true;
                 self.consumed.mod_keys.insert(mod_keys);
             }
-            Input::GamepadButton(button) => {
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            }
+            | Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
                 let input = GamepadInput {
                     gamepad: *self.gamepad_device,
                     input: button,
                 };
 
                 self.consumed.gamepad_buttons.insert(input);
+                self.consume_mod_buttons(mod_buttons);
             }
-            Input::GamepadAxis(axis) => {
+            Input::GamepadAxis { axis, mod_buttons } => {
                 let input = GamepadInput {
                     gamepad: *self.gamepad_device,
                     input: axis,
                 };
 
                 self.consumed.gamepad_axes.insert(input);
+                self.consume_mod_buttons(mod_buttons);
+            }
+            Input::Touch { .. } => self.consumed.touch = true,
+            Input::TouchDrag => self.consumed.touch_drag = true,
+            Input::TouchPinch => self.consumed.touch_pinch = true,
+            Input::TouchPan => self.consumed.touch_pan = true,
+            Input::Custom(id) => {
+                self.consumed.custom.insert(id);
+            }
+            Input::EntityPress { button, filter } => {
+                self.consumed.entity_presses.insert((button, filter));
             }
+            Input::Combo(combo) => {
+                for button in combo.iter() {
+                    self.consume(button);
+                }
+            }
+        }
+    }
+
+    fn consume_mod_buttons(&mut self, mod_buttons: GamepadModButtons) {
+        if !mod_buttons.is_empty() {
+            self.consumed
+                .gamepad_mod_buttons
+                .entry(*self.gamepad_device)
+                .or_default()
+                .insert(mod_buttons);
         }
     }
+
+    /// Returns the first input actuated during this frame that passes the settings filters.
+    pub(crate) fn capture(&self, settings: &CaptureSettings) -> Option<Input> {
+        let mod_keys = self
+            .keys
+            .get_pressed()
+            .map(|&key| ModKeys::from(key))
+            .collect();
+
+        let mut inputs = Vec::new();
+        if settings.devices.contains(CaptureDevices::KEYBOARD) {
+            for &key in self.keys.get_just_pressed() {
+                if ModKeys::from(key).is_empty() {
+                    inputs.push(Input::Keyboard { key, mod_keys });
+                }
+            }
+
+            // Modifiers can be captured only on release since they can be used with other keys.
+            for &key in self.keys.get_just_released() {
+                if !ModKeys::from(key).is_empty() {
+                    inputs.push(Input::Keyboard { key, mod_keys });
+                }
+            }
+        }
+
+        if settings.devices.contains(CaptureDevices::MOUSE) {
+            for &button in self.mouse_buttons.get_just_pressed() {
+                inputs.push(Input::MouseButton { button, mod_keys });
+            }
+
+            if self.mouse_wheel.length() >= settings.actuation {
+                inputs.push(Input::MouseWheel { mod_keys });
+            }
+        }
+
+        if settings.devices.contains(CaptureDevices::GAMEPAD) {
+            for (entity, gamepad) in &self.gamepads {
+                if !settings.gamepad.matches(entity) {
+                    continue;
+                }
+
+                let mod_buttons = gamepad
+                    .get_pressed()
+                    .map(|&button| GamepadModButtons::from(button))
+                    .collect();
+
+                for &button in gamepad.get_just_pressed() {
+                    if GamepadModButtons::from(button).is_empty() {
+                        inputs.push(Input::GamepadButton {
+                            button,
+                            mod_buttons,
+                        });
+                    }
+                }
+
+                // Modifiers are captured on release, similar to keyboard modifiers.
+                for &button in gamepad.get_just_released() {
+                    if !GamepadModButtons::from(button).is_empty() {
+                        inputs.push(Input::GamepadButton {
+                            button,
+                            mod_buttons,
+                        });
+                    }
+                }
+
+                for (&input, value) in gamepad.analog().all_axes_and_values() {
+                    if let gamepad::GamepadInput::Axis(axis) = input {
+                        if value.abs() >= settings.actuation {
+                            inputs.push(Input::GamepadAxis { axis, mod_buttons });
+                        }
+                    }
+                }
+            }
+        }
+
+        inputs
+            .into_iter()
+            .find(|input| !settings.excluded.contains(input))
+    }
+
+    /// Starts a new frame for [`InputRecorder`].
+    ///
+    /// Returns the time that should be used for the frame.
+    pub(crate) fn next_recorded_frame(&mut self, time: &Time<Virtual>) -> Time<Virtual> {
+        self.recorder.next_frame(time)
+    }
 }
 
-/// Tracks all consumed input from Bevy resources.
+fn has_gamepad_buttons(combo: InputCombo) -> bool {
+    combo
+        .iter()
+        .any(|button| matches!(button, ComboButton::GamepadButton(_)))
+}
+
+/// Frames at which [`ComboButton`]s were last pressed.
 ///
-/// Mouse motion and wheel can be consumed directly since we accumulate them.
+/// Gamepad buttons are stored per gamepad entity.
+#[derive(Default)]
+struct PressFrames {
+    frame: u32,
+    buttons: HashMap<(ComboButton, Option<Entity>), u32>,
+}
+
+/// Returns the analog value of the button or `1.0` if only the digital state reports it as pressed.
+fn analog_value(gamepad: &Gamepad, button: GamepadButton) -> f32 {
+    let value = gamepad.get(button).unwrap_or_default();
+    if value == 0.0 && gamepad.pressed(button) {
+        1.0
+    } else {
+        value
+    }
+}
+
+fn mod_buttons_pressed(gamepad: &Gamepad, mod_buttons: GamepadModButtons) -> bool {
+    mod_buttons
+        .iter_buttons()
+        .all(|button| gamepad.pressed(button))
+}
+
+/// Gestures calculated from [`Touches`].
+///
+/// We track positions ourselves because [`Touch::delta`] isn't
+/// updated on frames without touch events.
+#[derive(Default)]
+struct TouchState {
+    positions: HashMap<u64, Vec2>,
+    fingers: u8,
+    drag: Vec2,
+    pinch: f32,
+    pan: Vec2,
+}
+
+impl TouchState {
+    fn update(&mut self, touches: &Touches) {
+        let mut current = Vec::new();
+        let mut positions = HashMap::default();
+        for touch in touches.iter() {
+            let position = touch.position();
+            let previous = self.positions.get(&touch.id()).copied().unwrap_or(position);
+            current.push((previous, position));
+            positions.insert(touch.id(), position);
+        }
+        self.positions = positions;
+
+        self.fingers = current.len().try_into().unwrap_or(u8::MAX);
+        self.drag = Vec2::ZERO;
+        self.pinch = 0.0;
+        self.pan = Vec2::ZERO;
+
+        match *current.as_slice() {
+            [(previous, position)] => self.drag = position - previous,
+            [(previous1, position1), (previous2, position2)] => {
+                self.pinch = position1.distance(position2) - previous1.distance(previous2);
+                self.pan = ((position1 - previous1) + (position2 - previous2)) / 2.0;
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Tracks all consumed input from Bevy resources.
 #[derive(Resource, Default)]
 struct ConsumedInput {
     ui_wants_keyboard: bool,
     ui_wants_mouse: bool,
+    text_input: bool,
     keys: HashSet<KeyCode>,
     mod_keys: ModKeys,
     mouse_buttons: HashSet<MouseButton>,
+    mouse_motion: bool,
+    mouse_wheel: bool,
     gamepad_buttons: HashSet<GamepadInput<GamepadButton>>,
     gamepad_axes: HashSet<GamepadInput<GamepadAxis>>,
+    gamepad_mod_buttons: HashMap<GamepadDevice, GamepadModButtons>,
+    touch: bool,
+    touch_drag: bool,
+    touch_pinch: bool,
+    touch_pan: bool,
+    custom: HashSet<u32>,
+    entity_presses: HashSet<(MouseButton, u32)>,
 }
 
 impl ConsumedInput {
     fn reset(&mut self) {
         self.ui_wants_keyboard = false;
         self.ui_wants_mouse = false;
+        self.text_input = false;
         self.keys.clear();
         self.mod_keys = ModKeys::empty();
         self.mouse_buttons.clear();
+        self.mouse_motion = false;
+        self.mouse_wheel = false;
         self.gamepad_buttons.clear();
         self.gamepad_axes.clear();
+        self.gamepad_mod_buttons.clear();
+        self.touch = false;
+        self.touch_drag = false;
+        self.touch_pinch = false;
+        self.touch_pan = false;
+        self.custom.clear();
+        self.entity_presses.clear();
     }
 }
 
@@ -264,10 +871,22 @@ struct GamepadInput<T: Hash + Eq> {
 
 #[cfg(test)]
 mod tests {
-    use bevy::{ecs::system::SystemState, input::mouse::MouseScrollUnit};
+    use bevy::{
+        ecs::system::{RunSystemOnce, SystemState},
+        input::{
+            mouse::MouseScrollUnit,
+            touch::{touch_screen_input_system, TouchPhase},
+        },
+    };
 
     use super::*;
-    use crate::{input::InputModKeys, Input};
+    use crate::{
+        input::{
+            gamepad_set::GamepadSet, input_combo::InputCombo, input_source::InputSource,
+            InputModButtons, InputModKeys,
+        },
+        Input,
+    };
 
     #[test]
     fn keyboard() {
@@ -472,6 +1091,67 @@ mod tests {
         assert_eq!(reader.value(axis2), ActionValue::Axis1D(0.0));
     }
 
+    #[test]
+    fn gamepad_aggregation() {
+        let (mut world, mut state) = init_world();
+
+        let axis = GamepadAxis::LeftStickX;
+        let button = GamepadButton::RightTrigger2;
+        let mut gamepad1 = Gamepad::default();
+        gamepad1.analog_mut().set(axis, 0.5);
+        gamepad1.analog_mut().set(button, 0.2);
+        let gamepad1 = world.spawn(gamepad1).id();
+
+        let mut gamepad2 = Gamepad::default();
+        gamepad2.analog_mut().set(axis, -1.0);
+        gamepad2.analog_mut().set(button, 0.6);
+        world.spawn(gamepad2);
+
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(-0.5));
+        assert_eq!(
+            reader.value(Input::gamepad_analog(button)),
+            ActionValue::Axis1D(0.8)
+        );
+
+        *world.resource_mut::<GamepadAggregation>() = GamepadAggregation::MaxMagnitude;
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(-1.0));
+        assert_eq!(
+            reader.value(Input::gamepad_analog(button)),
+            ActionValue::Axis1D(0.6)
+        );
+
+        *world.resource_mut::<GamepadAggregation>() = GamepadAggregation::Average;
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(-0.25));
+        assert_eq!(
+            reader.value(Input::gamepad_analog(button)),
+            ActionValue::Axis1D(0.4)
+        );
+
+        *world.resource_mut::<GamepadAggregation>() = GamepadAggregation::MostRecent;
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(
+            reader.value(axis),
+            ActionValue::Axis1D(-1.0),
+            "should fall back to the largest value if no gamepad was actuated"
+        );
+
+        world
+            .get_mut::<Gamepad>(gamepad1)
+            .unwrap()
+            .digital_mut()
+            .press(GamepadButton::South);
+        let mut reader = state.get_mut(&mut world);
+        reader.update_actuated_devices(0.5);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(0.5));
+        assert_eq!(
+            reader.value(Input::gamepad_analog(button)),
+            ActionValue::Axis1D(0.2)
+        );
+    }
+
     #[test]
     fn keyboard_with_modifier() {
         let (mut world, mut state) = init_world();
@@ -504,7 +1184,7 @@ mod tests {
             .resource_mut::<ButtonInput<KeyCode>>()
             .press(other_key);
         let other_input = other_key.with_mod_keys(modifier.into());
-        let reader = state.get_mut(&mut world);
+        let mut reader = state.get_mut(&mut world);
         assert_eq!(reader.value(other_input), ActionValue::Bool(false));
         assert_eq!(reader.value(other_key), ActionValue::Bool(true));
     }
@@ -602,6 +1282,171 @@ mod tests {
         assert_eq!(reader.value(input), ActionValue::Axis2D(Vec2::ZERO));
     }
 
+    #[test]
+    fn gamepad_analog() {
+        let (mut world, mut state) = init_world();
+
+        let trigger = GamepadButton::RightTrigger2;
+        let button = GamepadButton::South;
+        let value = 0.4;
+        let mut gamepad = Gamepad::default();
+        gamepad.analog_mut().set(trigger, value);
+        gamepad.digital_mut().press(button);
+        world.spawn(gamepad);
+
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(
+            reader.value(Input::gamepad_analog(trigger)),
+            ActionValue::Axis1D(value)
+        );
+        assert_eq!(
+            reader.value(Input::gamepad_analog(button)),
+            ActionValue::Axis1D(1.0),
+            "digital state should be used without analog value"
+        );
+
+        reader.consume(trigger);
+        assert_eq!(
+            reader.value(Input::gamepad_analog(trigger)),
+            ActionValue::Axis1D(0.0)
+        );
+    }
+
+    #[test]
+    fn gamepad_button_with_modifier() {
+        let (mut world, mut state) = init_world();
+
+        let button = GamepadButton::South;
+        let modifier = GamepadButton::LeftTrigger;
+        let mut gamepad = Gamepad::default();
+        gamepad.digital_mut().press(modifier);
+        gamepad.digital_mut().press(button);
+        world.spawn(gamepad);
+
+        let input = button.with_mod_buttons(modifier.into());
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(input), ActionValue::Bool(true));
+        assert_eq!(reader.value(button), ActionValue::Bool(true));
+        assert_eq!(
+            reader.value(button.with_mod_buttons(GamepadModButtons::RIGHT_TRIGGER)),
+            ActionValue::Bool(false)
+        );
+
+        reader.consume(input);
+        assert_eq!(reader.value(input), ActionValue::Bool(false));
+
+        // Try another button, but with the same modifier that was consumed.
+        let other_button = GamepadButton::East;
+        let mut gamepad = world.query::<&mut Gamepad>().single_mut(&mut world);
+        gamepad.digital_mut().press(other_button);
+        let other_input = other_button.with_mod_buttons(modifier.into());
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(other_input), ActionValue::Bool(false));
+        assert_eq!(reader.value(other_button), ActionValue::Bool(true));
+    }
+
+    #[test]
+    fn gamepad_axis_with_modifier() {
+        let (mut world, mut state) = init_world();
+
+        let axis = GamepadAxis::LeftStickX;
+        let modifier = GamepadButton::RightTrigger2;
+        let value = 1.0;
+        let mut gamepad1 = Gamepad::default();
+        gamepad1.analog_mut().set(axis, value);
+        world.spawn(gamepad1);
+
+        let mut gamepad2 = Gamepad::default();
+        gamepad2.digital_mut().press(modifier);
+        world.spawn(gamepad2);
+
+        let input = axis.with_mod_buttons(modifier.into());
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(value));
+        assert_eq!(
+            reader.value(input),
+            ActionValue::Axis1D(0.0),
+            "modifier should be held on the same gamepad"
+        );
+
+        let mut gamepads = world.query::<&mut Gamepad>();
+        for mut gamepad in gamepads.iter_mut(&mut world) {
+            gamepad.digital_mut().press(modifier);
+        }
+
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(input), ActionValue::Axis1D(value));
+    }
+
+    #[test]
+    fn combo() {
+        let (mut world, mut state) = init_world();
+
+        let key = KeyCode::KeyG;
+        let button = GamepadButton::South;
+        world.resource_mut::<ButtonInput<KeyCode>>().press(key);
+        let mut gamepad = Gamepad::default();
+        gamepad.digital_mut().press(button);
+        let gamepad_entity = world.spawn(gamepad).id();
+
+        let combo = InputCombo::default().with(key).with(button);
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(combo), ActionValue::Bool(true));
+        assert_eq!(reader.gamepad_entity(combo.into()), Some(gamepad_entity));
+        assert_eq!(
+            reader.value(combo.with(KeyCode::KeyH)),
+            ActionValue::Bool(false)
+        );
+
+        reader.consume(combo);
+        assert_eq!(reader.value(combo), ActionValue::Bool(false));
+        assert_eq!(
+            reader.value(key),
+            ActionValue::Bool(false),
+            "consuming a combo should consume all its buttons"
+        );
+    }
+
+    #[test]
+    fn empty_combo() {
+        let (mut world, mut state) = init_world();
+
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(
+            reader.value(InputCombo::default()),
+            ActionValue::Bool(false)
+        );
+    }
+
+    #[test]
+    fn ordered_combo() {
+        let (mut world, mut state) = init_world();
+
+        let first = KeyCode::KeyA;
+        let second = MouseButton::Left;
+        world.resource_mut::<ButtonInput<KeyCode>>().press(first);
+        state.get_mut(&mut world).update_state();
+
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(second);
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+
+        let combo = InputCombo::default().with(first).with(second).ordered();
+        assert_eq!(reader.value(combo), ActionValue::Bool(true));
+
+        let reversed = InputCombo::default().with(second).with(first).ordered();
+        assert_eq!(reader.value(reversed), ActionValue::Bool(false));
+        let unordered = InputCombo::default().with(second).with(first);
+        assert_eq!(
+            reader.value(unordered),
+            ActionValue::Bool(true),
+            "unordered combo should ignore press order"
+        );
+    }
+
     #[test]
     fn ui_input() {
         let (mut world, mut state) = init_world();
@@ -635,6 +1480,253 @@ mod tests {
             reader.value(Input::mouse_wheel()),
             ActionValue::Axis2D(Vec2::ZERO)
         );
+
+        reader.set_ignore_ui_focus(true);
+        assert_eq!(reader.value(key), ActionValue::Bool(true));
+        assert_eq!(reader.value(button), ActionValue::Bool(true));
+    }
+
+    #[test]
+    fn blocked() {
+        let (mut world, mut state) = init_world();
+
+        let key = KeyCode::Space;
+        let button = MouseButton::Left;
+        world.resource_mut::<ButtonInput<KeyCode>>().press(key);
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(button);
+
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        reader.set_ignore_ui_focus(true);
+        reader.set_blocked(true);
+
+        assert_eq!(reader.value(key), ActionValue::Bool(false));
+        assert_eq!(reader.value(button), ActionValue::Bool(false));
+
+        reader.set_blocked(false);
+        assert_eq!(reader.value(key), ActionValue::Bool(true));
+    }
+
+    #[test]
+    fn text_input() {
+        let (mut world, mut state) = init_world();
+
+        let key = KeyCode::KeyW;
+        let button = MouseButton::Left;
+        world.resource_mut::<ButtonInput<KeyCode>>().press(key);
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(button);
+
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        reader.consumed.text_input = true;
+
+        assert_eq!(reader.value(key), ActionValue::Bool(false));
+        assert_eq!(
+            reader.value(button.with_mod_keys(ModKeys::CONTROL)),
+            ActionValue::Bool(false)
+        );
+        assert_eq!(reader.value(button), ActionValue::Bool(true));
+
+        reader.set_active_in_text_input(true);
+        assert_eq!(reader.value(key), ActionValue::Bool(true));
+    }
+
+    #[test]
+    fn touch() {
+        let (mut world, mut state) = init_world();
+
+        send_touch(&mut world, 0, TouchPhase::Started, Vec2::ZERO);
+
+        let input = Input::touch();
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        assert_eq!(reader.value(input), ActionValue::Bool(true));
+        assert_eq!(
+            reader.value(Input::Touch { fingers: 2 }),
+            ActionValue::Bool(false)
+        );
+
+        reader.consume(input);
+        assert_eq!(reader.value(input), ActionValue::Bool(false));
+    }
+
+    #[test]
+    fn touch_drag() {
+        let (mut world, mut state) = init_world();
+
+        send_touch(&mut world, 0, TouchPhase::Started, Vec2::ZERO);
+        state.get_mut(&mut world).update_state();
+
+        let value = Vec2::ONE;
+        send_touch(&mut world, 0, TouchPhase::Moved, value);
+
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        assert_eq!(reader.value(Input::TouchDrag), ActionValue::Axis2D(value));
+        assert_eq!(
+            reader.value(Input::TouchPan),
+            ActionValue::Axis2D(Vec2::ZERO)
+        );
+
+        reader.consume(Input::TouchDrag);
+        assert_eq!(
+            reader.value(Input::TouchDrag),
+            ActionValue::Axis2D(Vec2::ZERO)
+        );
+
+        reader.update_state();
+        assert_eq!(
+            reader.value(Input::TouchDrag),
+            ActionValue::Axis2D(Vec2::ZERO),
+            "should reset without movement"
+        );
+    }
+
+    #[test]
+    fn touch_pinch_and_pan() {
+        let (mut world, mut state) = init_world();
+
+        send_touch(&mut world, 0, TouchPhase::Started, Vec2::ZERO);
+        send_touch(&mut world, 1, TouchPhase::Started, Vec2::X);
+        state.get_mut(&mut world).update_state();
+
+        send_touch(&mut world, 0, TouchPhase::Moved, Vec2::new(-1.0, 1.0));
+        send_touch(&mut world, 1, TouchPhase::Moved, Vec2::new(3.0, 1.0));
+
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        assert_eq!(reader.value(Input::TouchPinch), ActionValue::Axis1D(3.0));
+        assert_eq!(
+            reader.value(Input::TouchPan),
+            ActionValue::Axis2D(Vec2::new(0.5, 1.0))
+        );
+        assert_eq!(
+            reader.value(Input::TouchDrag),
+            ActionValue::Axis2D(Vec2::ZERO)
+        );
+        assert_eq!(
+            reader.value(Input::Touch { fingers: 2 }),
+            ActionValue::Bool(true)
+        );
+
+        reader.consume(Input::TouchPinch);
+        assert_eq!(reader.value(Input::TouchPinch), ActionValue::Axis1D(0.0));
+
+        reader.consume(Input::TouchPan);
+        assert_eq!(
+            reader.value(Input::TouchPan),
+            ActionValue::Axis2D(Vec2::ZERO)
+        );
+    }
+
+    #[test]
+    fn virtual_input() {
+        let (mut world, mut state) = init_world();
+
+        let key = KeyCode::Space;
+        let axis = GamepadAxis::LeftStickX;
+        let gamepad_entity = world.spawn_empty().id();
+        let mut virtual_input = world.resource_mut::<VirtualInput>();
+        virtual_input.insert(key, true);
+        virtual_input.insert_gamepad(gamepad_entity, axis, 1.0);
+
+        let mut reader = state.get_mut(&mut world);
+        reader.update_state();
+        assert_eq!(reader.value(key), ActionValue::Bool(true));
+        assert_eq!(reader.value(KeyCode::Escape), ActionValue::Bool(false));
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(1.0));
+
+        reader.set_gamepad(Entity::PLACEHOLDER);
+        assert_eq!(
+            reader.value(axis),
+            ActionValue::Axis1D(0.0),
+            "should read only from `{gamepad_entity:?}`"
+        );
+
+        reader.set_gamepad(gamepad_entity);
+        reader.consume(axis);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(0.0));
+
+        reader.consume(key);
+        assert_eq!(reader.value(key), ActionValue::Bool(false));
+
+        reader.update_state();
+        assert_eq!(
+            reader.value(key),
+            ActionValue::Bool(true),
+            "injected values should persist until removed"
+        );
+    }
+
+    #[test]
+    fn virtual_input_aggregation() {
+        let (mut world, mut state) = init_world();
+
+        let axis = GamepadAxis::LeftStickX;
+        let button = GamepadButton::South;
+        let gamepad1 = world.spawn_empty().id();
+        let gamepad2 = world.spawn_empty().id();
+        let mut virtual_input = world.resource_mut::<VirtualInput>();
+        virtual_input.insert_gamepad(gamepad1, axis, 0.5);
+        virtual_input.insert_gamepad(gamepad2, axis, -1.0);
+        virtual_input.insert_gamepad(gamepad2, button, true);
+
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(-0.5));
+        assert_eq!(reader.value(button), ActionValue::Bool(true));
+
+        *world.resource_mut::<GamepadAggregation>() = GamepadAggregation::MaxMagnitude;
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(-1.0));
+
+        reader.set_gamepad(GamepadDevice::Except(GamepadSet::default().with(gamepad2)));
+        assert_eq!(reader.value(axis), ActionValue::Axis1D(0.5));
+        assert_eq!(reader.value(button), ActionValue::Bool(false));
+    }
+
+    #[test]
+    fn custom() {
+        let (mut world, mut state) = init_world();
+
+        world
+            .resource_mut::<InputSources>()
+            .0
+            .push(Box::new(DummySource));
+
+        let input = Input::Custom(DummySource::ID);
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.value(input), ActionValue::Axis1D(1.0));
+        assert_eq!(reader.value(Input::Custom(1)), ActionValue::Bool(false));
+
+        reader.consume(input);
+        assert_eq!(reader.value(input), ActionValue::Axis1D(0.0));
+    }
+
+    fn send_touch(world: &mut World, id: u64, phase: TouchPhase, position: Vec2) {
+        world.send_event(TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        });
+        world.run_system_once(touch_screen_input_system).unwrap();
+    }
+
+    struct DummySource;
+
+    impl DummySource {
+        const ID: u32 = 0;
+    }
+
+    impl InputSource for DummySource {
+        fn value(&self, id: u32) -> Option<ActionValue> {
+            (id == Self::ID).then_some(1.0.into())
+        }
     }
 
     fn init_world<'w, 's>() -> (World, SystemState<InputReader<'w, 's>>) {
@@ -645,6 +1737,14 @@ mod tests {
         world.init_resource::<Events<MouseWheel>>();
         world.init_resource::<ButtonInput<GamepadButton>>();
         world.init_resource::<Axis<GamepadAxis>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<Events<TouchInput>>();
+        world.init_resource::<VirtualInput>();
+        world.init_resource::<InputSources>();
+        world.init_resource::<GamepadAggregation>();
+        world.init_resource::<InputFocus>();
+        world.init_resource::<TextInputMode>();
+        world.init_resource::<InputRecorder>();
 
         let state = SystemState::<InputReader>::new(&mut world);
 
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{GamepadDevice, Input};
+use crate::action_value::ActionValue;
+
+/// Records input values and replays them.
+///
+/// While recording, every value read from devices is stored along with the [`Time<Virtual>`] delta
+/// for each frame. While playing, device input is ignored and the recorded values are used instead.
+///
+/// Since playback goes through the same pipeline as real input, all modifiers and conditions,
+/// including time-based ones like [`Hold`](crate::input_context::input_condition::hold::Hold),
+/// will behave exactly as during recording. The playback stops automatically after the last frame.
+///
+/// Recordings store gamepad entities as is, so when replaying in a different session
+/// contexts should use [`GamepadDevice::Any`] or the same entities.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn toggle_recording(mut recorder: ResMut<InputRecorder>) {
+///     if let Some(recording) = recorder.stop_recording() {
+///         // Can be serialized into any format supported by serde.
+///         recorder.start_playback(recording);
+///     } else {
+///         recorder.start_recording();
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct InputRecorder {
+    state: RecorderState,
+}
+
+impl InputRecorder {
+    /// Starts a new recording, stopping any active recording or playback.
+    pub fn start_recording(&mut self) {
+        debug!("starting input recording");
+        self.state = RecorderState::Recording(Default::default());
+    }
+
+    /// Stops the active recording and returns it.
+    ///
+    /// Returns [`None`] if nothing is being recorded.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        match std::mem::take(&mut self.state) {
+            RecorderState::Recording(recording) => {
+                debug!("stopping input recording");
+                Some(recording)
+            }
+            state => {
+                self.state = state;
+                None
+            }
+        }
+    }
+
+    /// Starts replaying the recording, stopping any active recording or playback.
+    pub fn start_playback(&mut self, recording: InputRecording) {
+        debug!(
+            "starting playback of {} recorded frames",
+            recording.frames.len()
+        );
+        self.state = RecorderState::Playing {
+            recording,
+            frame: None,
+        };
+    }
+
+    /// Stops the active playback.
+    pub fn stop_playback(&mut self) {
+        if self.is_playing() {
+            debug!("stopping playback");
+            self.state = RecorderState::Idle;
+        }
+    }
+
+    /// Returns `true` if inputs are being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, RecorderState::Recording(_))
+    }
+
+    /// Returns `true` if a recording is being replayed.
+    pub fn is_playing(&self) -> bool {
+        matches!(self.state, RecorderState::Playing { .. })
+    }
+
+    /// Starts a new frame.
+    ///
+    /// Returns the time that should be used for the frame,
+    /// which is the recorded time during playback.
+    pub(super) fn next_frame(&mut self, time: &Time<Virtual>) -> Time<Virtual> {
+        match &mut self.state {
+            RecorderState::Idle => *time,
+            RecorderState::Recording(recording) => {
+                recording.frames.push(RecordedFrame {
+                    delta: time.delta(),
+                    relative_speed: time.relative_speed(),
+                    values: Default::default(),
+                });
+                *time
+            }
+            RecorderState::Playing { recording, frame } => {
+                let index = frame.map_or(0, |index| index + 1);
+                let Some(recorded_frame) = recording.frames.get(index) else {
+                    debug!("finishing playback");
+                    self.state = RecorderState::Idle;
+                    return *time;
+                };
+
+                *frame = Some(index);
+
+                let mut time = Time::<Virtual>::default();
+                time.set_relative_speed(recorded_frame.relative_speed);
+                time.advance_by(recorded_frame.delta);
+                time
+            }
+        }
+    }
+
+    /// Returns the recorded value if a recording is being replayed.
+    ///
+    /// Returns zero for inputs that weren't recorded for the current frame.
+    pub(super) fn playback_value(
+        &self,
+        gamepad: GamepadDevice,
+        input: Input,
+    ) -> Option<ActionValue> {
+        let RecorderState::Playing {
+            recording,
+            frame: Some(frame),
+        } = &self.state
+        else {
+            return None;
+        };
+
+        let value = recording.frames[*frame]
+            .values
+            .iter()
+            .find(|value| value.gamepad == gamepad && value.input == input)
+            .map(|value| value.value)
+            .unwrap_or_else(|| input.zero_value());
+
+        Some(value)
+    }
+
+    /// Stores the value for the current frame if inputs are being recorded.
+    pub(super) fn record(&mut self, gamepad: GamepadDevice, input: Input, value: ActionValue) {
+        let RecorderState::Recording(recording) = &mut self.state else {
+            return;
+        };
+
+        // Zero values are not stored to reduce the recording size.
+        if !value.as_bool() {
+            return;
+        }
+
+        let Some(frame) = recording.frames.last_mut() else {
+            return;
+        };
+
+        if !frame
+            .values
+            .iter()
+            .any(|value| value.gamepad == gamepad && value.input == input)
+        {
+            frame.values.push(RecordedValue {
+                gamepad,
+                input,
+                value,
+            });
+        }
+    }
+}
+
+#[derive(Default)]
+enum RecorderState {
+    #[default]
+    Idle,
+    Recording(InputRecording),
+    Playing {
+        recording: InputRecording,
+        frame: Option<usize>,
+    },
+}
+
+/// Recorded input produced by [`InputRecorder`].
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct InputRecording {
+    /// Recorded frames in the order they were captured.
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// Input values for a single frame of [`InputRecording`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedFrame {
+    /// Value of [`Time::delta`] for this frame.
+    pub delta: Duration,
+
+    /// Value of [`Time::relative_speed`] for this frame.
+    pub relative_speed: f32,
+
+    /// Non-zero values read during this frame.
+    pub values: Vec<RecordedValue>,
+}
+
+/// Value read from a device.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RecordedValue {
+    /// Gamepad from which the input was read.
+    pub gamepad: GamepadDevice,
+
+    /// Input from which the value was read.
+    pub input: Input,
+
+    /// Value of the input during the frame.
+    pub value: ActionValue,
+}
@@ -0,0 +1,113 @@
+use std::any;
+
+use bevy::prelude::*;
+
+use crate::action_value::ActionValue;
+
+/// An extension trait for [`App`] to register custom input sources.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// let mut app = App::new();
+/// app.add_input_source(RemoteController::default());
+/// # #[derive(Default)]
+/// # struct RemoteController;
+/// # impl InputSource for RemoteController {
+/// # fn value(&self, _id: u32) -> Option<ActionValue> { None }
+/// # }
+/// ```
+pub trait InputSourceAppExt {
+    /// Registers a source for [`Input::Custom`](super::Input::Custom).
+    ///
+    /// Sources are queried in the registration order.
+    fn add_input_source(&mut self, source: impl InputSource) -> &mut Self;
+}
+
+impl InputSourceAppExt for App {
+    fn add_input_source(&mut self, source: impl InputSource) -> &mut Self {
+        debug!(
+            "registering input source `{}`",
+            any::type_name_of_val(&source)
+        );
+
+        self.world_mut()
+            .get_resource_or_init::<InputSources>()
+            .0
+            .push(Box::new(source));
+
+        self
+    }
+}
+
+/// Provides values for [`Input::Custom`](super::Input::Custom).
+///
+/// Can be used to add new devices, such as a network-driven controller or a debug console.
+///
+/// Consumption is handled automatically by ID, just like for other inputs.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// #[derive(Default)]
+/// struct RemoteController {
+///     jump_pressed: bool,
+/// }
+///
+/// impl RemoteController {
+///     const JUMP: u32 = 0;
+/// }
+///
+/// impl InputSource for RemoteController {
+///     fn update(&mut self, world: &World) {
+///         let connection = world.resource::<Connection>();
+///         self.jump_pressed = connection.jump_pressed;
+///     }
+///
+///     fn value(&self, id: u32) -> Option<ActionValue> {
+///         match id {
+///             Self::JUMP => Some(self.jump_pressed.into()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// # let mut ctx = ContextInstance::default();
+/// ctx.bind::<Jump>().to(Input::Custom(RemoteController::JUMP));
+/// # #[derive(Resource)]
+/// # struct Connection { jump_pressed: bool }
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct Jump;
+/// ```
+pub trait InputSource: Send + Sync + 'static {
+    /// Updates the internal state.
+    ///
+    /// Called once per frame before the inputs are read.
+    fn update(&mut self, _world: &World) {}
+
+    /// Returns the value for the custom input ID.
+    ///
+    /// Returns [`None`] if the ID is not provided by this source.
+    fn value(&self, id: u32) -> Option<ActionValue>;
+}
+
+/// Stores registered [`InputSource`]s.
+#[derive(Resource, Default)]
+pub(crate) struct InputSources(pub(super) Vec<Box<dyn InputSource>>);
+
+impl InputSources {
+    /// Updates all sources.
+    pub(crate) fn update(world: &mut World) {
+        world.resource_scope(|world, mut sources: Mut<Self>| {
+            for source in &mut sources.0 {
+                source.update(world);
+            }
+        });
+    }
+
+    /// Returns the value from the first source that provides the ID.
+    pub(super) fn value(&self, id: u32) -> Option<ActionValue> {
+        self.0.iter().find_map(|source| source.value(id))
+    }
+}
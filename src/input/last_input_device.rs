@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::GamepadDevice;
+
+/// Tracks the device that was used last by any player.
+///
+/// Useful for switching on-screen prompts between keyboard and gamepad glyphs.
+/// Triggers untargeted [`InputDeviceChanged`] when the device changes.
+///
+/// Each [`ContextInstance`](crate::input_context::context_instance::ContextInstance) also tracks
+/// the last device that matches its gamepad, see
+/// [`ContextInstance::last_device`](crate::input_context::context_instance::ContextInstance::last_device).
+///
+/// A device is considered used when a button is pressed, the mouse is moved or scrolled, a finger touches
+/// the screen or a gamepad axis exceeds [`Self::actuation`].
+#[derive(Resource)]
+pub struct LastInputDevice {
+    device: Option<InputDevice>,
+    actuation: f32,
+}
+
+impl LastInputDevice {
+    /// Returns the last used device.
+    ///
+    /// [`None`] if no device has been used yet.
+    pub fn get(&self) -> Option<InputDevice> {
+        self.device
+    }
+
+    /// Returns the minimum absolute value of a gamepad axis to consider the gamepad used.
+    pub fn actuation(&self) -> f32 {
+        self.actuation
+    }
+
+    /// Sets the minimum absolute value of a gamepad axis to consider the gamepad used.
+    ///
+    /// Prevents stick drift from switching the device.
+    ///
+    /// By default it's 0.3.
+    pub fn set_actuation(&mut self, actuation: f32) {
+        self.actuation = actuation;
+    }
+
+    /// Selects the device from the devices used during this frame and triggers [`InputDeviceChanged`] on change.
+    pub(crate) fn update(&mut self, commands: &mut Commands, actuated: &[InputDevice]) {
+        let device = next_device(self.device, actuated.iter().copied());
+        if device != self.device {
+            self.device = device;
+            if let Some(device) = device {
+                debug!("changing last input device to `{device:?}`");
+                commands.trigger(InputDeviceChanged { device });
+            }
+        }
+    }
+}
+
+impl Default for LastInputDevice {
+    fn default() -> Self {
+        Self {
+            device: None,
+            actuation: 0.3,
+        }
+    }
+}
+
+/// Returns `current` if it was actuated, otherwise the first actuated device.
+///
+/// Keeps `current` if nothing was actuated.
+pub(crate) fn next_device(
+    current: Option<InputDevice>,
+    actuated: impl Iterator<Item = InputDevice>,
+) -> Option<InputDevice> {
+    let mut first = None;
+    for device in actuated {
+        if Some(device) == current {
+            return current;
+        }
+        first.get_or_insert(device);
+    }
+
+    first.or(current)
+}
+
+/// Kind of a physical device.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// Any keyboard.
+    Keyboard,
+    /// Any mouse.
+    Mouse,
+    /// Gamepad with the specified entity.
+    Gamepad(Entity),
+    /// Any touch screen.
+    Touch,
+}
+
+impl InputDevice {
+    /// Returns `true` if this device can be read for the specified gamepad.
+    ///
+    /// Always returns `true` for non-gamepad devices.
+    pub fn matches(self, gamepad: GamepadDevice) -> bool {
+        match self {
+            InputDevice::Gamepad(entity) => gamepad.matches(entity),
+            InputDevice::Keyboard | InputDevice::Mouse | InputDevice::Touch => true,
+        }
+    }
+}
+
+/// Triggers when the last used device changes.
+///
+/// Triggered without a target for [`LastInputDevice`] and for each context entity
+/// for [`ContextInstance::last_device`](crate::input_context::context_instance::ContextInstance::last_device).
+#[derive(Debug, Event, Clone, Copy)]
+pub struct InputDeviceChanged {
+    /// New device.
+    pub device: InputDevice,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next() {
+        let gamepad = InputDevice::Gamepad(Entity::PLACEHOLDER);
+        assert_eq!(next_device(None, [].into_iter()), None);
+        assert_eq!(
+            next_device(None, [InputDevice::Mouse, gamepad].into_iter()),
+            Some(InputDevice::Mouse)
+        );
+        assert_eq!(
+            next_device(Some(gamepad), [InputDevice::Mouse, gamepad].into_iter()),
+            Some(gamepad),
+            "current device should be kept if it's still used"
+        );
+        assert_eq!(next_device(Some(gamepad), [].into_iter()), Some(gamepad));
+    }
+}
@@ -0,0 +1,186 @@
+use std::{any, mem};
+
+use bevy::{
+    ecs::query::QueryFilter,
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use super::Input;
+
+/// An extension trait for [`App`] to register entity filters for [`Input::EntityPress`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// let mut app = App::new();
+/// app.add_entity_filter::<With<Unit>>(Unit::FILTER);
+/// # #[derive(Component)]
+/// # struct Unit;
+/// # impl Unit { const FILTER: u32 = 0; }
+/// ```
+pub trait PickingAppExt {
+    /// Registers a query filter that entities should match to be pressed via
+    /// [`Input::EntityPress`] with the given ID.
+    fn add_entity_filter<F: QueryFilter + 'static>(&mut self, id: u32) -> &mut Self;
+}
+
+impl PickingAppExt for App {
+    fn add_entity_filter<F: QueryFilter + 'static>(&mut self, id: u32) -> &mut Self {
+        debug!(
+            "registering entity filter `{}` with ID {id}",
+            any::type_name::<F>()
+        );
+
+        let state = QueryState::<(), F>::new(self.world_mut());
+        self.world_mut()
+            .get_resource_or_init::<PressedEntities>()
+            .filters
+            .push((id, Box::new(state)));
+
+        self
+    }
+}
+
+/// Tracks entities pressed for [`Input::EntityPress`].
+///
+/// When a pointer button is pressed, the nearest entity from [`HoverMap`] that matches
+/// the registered filter is remembered until the button is released. Since [`HoverMap`]
+/// respects blocking, entities behind UI nodes can't be pressed.
+///
+/// Requires picking plugins and a backend that reports hits for the desired entities.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn select_unit(
+///     trigger: Trigger<Started<Select>>,
+///     mut commands: Commands,
+///     pressed: Res<PressedEntities>,
+/// ) {
+///     let event = trigger.event();
+///     if let Some(unit) = event.inputs.iter().find_map(|&input| pressed.entity(input)) {
+///         commands.entity(unit).insert(Selected);
+///     }
+/// }
+/// # #[derive(Component)]
+/// # struct Selected;
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct Select;
+/// ```
+#[derive(Resource, Default)]
+pub struct PressedEntities {
+    filters: Vec<(u32, Box<dyn EntityFilter>)>,
+    presses: HashMap<(MouseButton, u32), EntityPress>,
+    held: HashSet<(PointerId, MouseButton)>,
+}
+
+impl PressedEntities {
+    /// Returns the last entity pressed with the input.
+    ///
+    /// Preserved after the release, so it's available in
+    /// [`Completed`](crate::input_context::events::Completed).
+    /// Returns [`None`] if the input isn't [`Input::EntityPress`] or nothing was pressed yet.
+    pub fn entity(&self, input: Input) -> Option<Entity> {
+        let Input::EntityPress { button, filter } = input else {
+            return None;
+        };
+
+        self.presses
+            .get(&(button, filter))
+            .map(|press| press.entity)
+    }
+
+    /// Returns `true` if an entity that matches the filter is currently pressed with the button.
+    pub(super) fn is_pressed(&self, button: MouseButton, filter: u32) -> bool {
+        self.presses
+            .get(&(button, filter))
+            .is_some_and(|press| press.pointer.is_some())
+    }
+
+    pub(crate) fn update(world: &mut World) {
+        world.resource_scope(|world, mut pressed: Mut<Self>| {
+            let mouse_buttons = world.resource::<ButtonInput<MouseButton>>();
+            let touches = world.resource::<Touches>();
+
+            for (&(button, _), press) in &mut pressed.presses {
+                let released = match press.pointer {
+                    Some(PointerId::Mouse) => !mouse_buttons.pressed(button),
+                    Some(PointerId::Touch(id)) => touches.get_pressed(id).is_none(),
+                    Some(PointerId::Custom(_)) | None => true,
+                };
+                if released {
+                    press.pointer = None;
+                }
+            }
+
+            // Compare with the previous frame instead of using `just_pressed`
+            // to also catch presses that happened before the input was processed.
+            let held: HashSet<_> = mouse_buttons
+                .get_pressed()
+                .map(|&button| (PointerId::Mouse, button))
+                .chain(
+                    touches
+                        .iter()
+                        .map(|touch| (PointerId::Touch(touch.id()), MouseButton::Left)),
+                )
+                .collect();
+            let previous = mem::replace(&mut pressed.held, held);
+
+            let Some(hover_map) = world.get_resource::<HoverMap>() else {
+                return;
+            };
+
+            let pressed = &mut *pressed;
+            for &(pointer, button) in pressed.held.difference(&previous) {
+                let Some(hovered) = hover_map.get(&pointer) else {
+                    continue;
+                };
+
+                let mut hovered: Vec<_> = hovered
+                    .iter()
+                    .map(|(&entity, hit)| (entity, hit.depth))
+                    .collect();
+                hovered.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+                for (id, filter) in &mut pressed.filters {
+                    if let Some((entity, _)) = hovered
+                        .iter()
+                        .find(|&&(entity, _)| filter.matches(world, entity))
+                    {
+                        trace!("pressing `{entity}` with `{button:?}` for filter {id}");
+                        pressed.presses.insert(
+                            (button, *id),
+                            EntityPress {
+                                entity: *entity,
+                                pointer: Some(pointer),
+                            },
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Entity pressed by a pointer.
+struct EntityPress {
+    entity: Entity,
+
+    /// Pointer that holds the press or [`None`] if released.
+    pointer: Option<PointerId>,
+}
+
+/// Type-erased [`QueryState`] to check entities against a filter.
+trait EntityFilter: Send + Sync {
+    fn matches(&mut self, world: &World, entity: Entity) -> bool;
+}
+
+impl<F: QueryFilter> EntityFilter for QueryState<(), F> {
+    fn matches(&mut self, world: &World, entity: Entity) -> bool {
+        self.update_archetypes(world);
+        self.get_manual(world, entity).is_ok()
+    }
+}
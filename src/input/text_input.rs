@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+/// Controls whether the keyboard is used for text entry, like typing into a chat box.
+///
+/// While active, keyboard inputs and [`ModKeys`](super::ModKeys) read zero, unless
+/// the action opts out with
+/// [`InputAction::ACTIVE_IN_TEXT_INPUT`](crate::input_context::input_action::InputAction::ACTIVE_IN_TEXT_INPUT).
+/// Keys that are still held when the mode ends are ignored until released,
+/// so the key that closed a chat box won't trigger gameplay actions.
+///
+/// The mode is active if it's enabled manually via [`Self::set_enabled`] or
+/// if IME is enabled on the focused window and [`Self::detect_ime`] is on.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn open_chat(_trigger: Trigger<Started<OpenChat>>, mut text_input: ResMut<TextInputMode>) {
+///     text_input.set_enabled(true);
+/// }
+///
+/// fn close_chat(_trigger: Trigger<Started<CloseChat>>, mut text_input: ResMut<TextInputMode>) {
+///     text_input.set_enabled(false);
+/// }
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct OpenChat;
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool, active_in_text_input = true)]
+/// # struct CloseChat;
+/// ```
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct TextInputMode {
+    enabled: bool,
+    detect_ime: bool,
+    active: bool,
+}
+
+impl TextInputMode {
+    /// Returns `true` if the keyboard is currently used for text entry.
+    ///
+    /// Updated once per frame before contexts are evaluated.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns `true` if the mode was enabled manually.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the mode manually.
+    ///
+    /// Applied on the next update.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if IME detection is enabled.
+    pub fn detect_ime(&self) -> bool {
+        self.detect_ime
+    }
+
+    /// Sets whether the mode should be active while
+    /// [`Window::ime_enabled`] is set on the focused window.
+    ///
+    /// By default it's `true`.
+    pub fn set_detect_ime(&mut self, detect_ime: bool) {
+        self.detect_ime = detect_ime;
+    }
+
+    pub(crate) fn update(mut mode: ResMut<Self>, windows: Query<&Window>) {
+        let ime_active = mode.detect_ime
+            && windows
+                .iter()
+                .any(|window| window.focused && window.ime_enabled);
+
+        let active = mode.enabled || ime_active;
+        if mode.active != active {
+            debug!("changing text input mode to `{active}`");
+            mode.active = active;
+        }
+    }
+}
+
+impl Default for TextInputMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            detect_ime: true,
+            active: false,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{gamepad_aggregation::GamepadAggregation, GamepadDevice, Input};
+use crate::action_value::{ActionValue, ActionValueDim};
+
+/// Injects input values without a physical device.
+///
+/// Useful for AI-controlled characters, tests or replays.
+/// Injected values go through the same modifiers, conditions and consumption
+/// as the inputs from real devices.
+///
+/// Values persist until removed, similar to held buttons. For inputs that are
+/// usually read as deltas, like [`Input::MouseMotion`], the value will be applied every frame.
+///
+/// If the device reports a non-zero value for the same input, the device value takes precedence.
+/// Values injected for multiple gamepads are combined according to [`GamepadAggregation`]
+/// when read from [`GamepadDevice::Any`], [`GamepadDevice::Set`] or [`GamepadDevice::Except`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// fn jump(mut virtual_input: ResMut<VirtualInput>) {
+///     virtual_input.insert(KeyCode::Space, true);
+/// }
+///
+/// fn ai_move(mut virtual_input: ResMut<VirtualInput>, ai_gamepad: Single<Entity, With<AiGamepad>>) {
+///     // Gamepad inputs can be injected for a specific entity
+///     // that is assigned to a context using `ContextInstance::set_gamepad`.
+///     virtual_input.insert_gamepad(*ai_gamepad, GamepadAxis::LeftStickX, 1.0);
+/// }
+/// # #[derive(Component)]
+/// # struct AiGamepad;
+/// ```
+#[derive(Resource, Default, Clone)]
+pub struct VirtualInput(HashMap<(GamepadDevice, Input), ActionValue>);
+
+impl VirtualInput {
+    /// Injects a value for the input.
+    ///
+    /// For gamepad inputs the value will be available for all gamepads.
+    /// Use [`Self::insert_gamepad`] to inject for a specific gamepad.
+    ///
+    /// Returns previously injected value if present.
+    pub fn insert(
+        &mut self,
+        input: impl Into<Input>,
+        value: impl Into<ActionValue>,
+    ) -> Option<ActionValue> {
+        self.0
+            .insert((GamepadDevice::Any, input.into()), value.into())
+    }
+
+    /// Injects a value for the input from a specific gamepad.
+    ///
+    /// The gamepad entity doesn't need to have a [`Gamepad`] component,
+    /// so any entity can act as a virtual gamepad.
+    ///
+    /// Returns previously injected value if present.
+    pub fn insert_gamepad(
+        &mut self,
+        gamepad_entity: Entity,
+        input: impl Into<Input>,
+        value: impl Into<ActionValue>,
+    ) -> Option<ActionValue> {
+        self.0.insert(
+            (GamepadDevice::Single(gamepad_entity), input.into()),
+            value.into(),
+        )
+    }
+
+    /// Removes the injected value for the input.
+    ///
+    /// Returns the removed value if present.
+    pub fn remove(&mut self, input: impl Into<Input>) -> Option<ActionValue> {
+        self.0.remove(&(GamepadDevice::Any, input.into()))
+    }
+
+    /// Removes the injected value for the input from a specific gamepad.
+    ///
+    /// Returns the removed value if present.
+    pub fn remove_gamepad(
+        &mut self,
+        gamepad_entity: Entity,
+        input: impl Into<Input>,
+    ) -> Option<ActionValue> {
+        self.0
+            .remove(&(GamepadDevice::Single(gamepad_entity), input.into()))
+    }
+
+    /// Removes all injected values.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the injected value for the input read from the specified gamepad converted into `dim`.
+    ///
+    /// Values from multiple matching gamepads are combined like values from physical gamepads:
+    /// booleans are `true` if any of them is `true`, axes are combined using `aggregation`.
+    pub(super) fn get(
+        &self,
+        gamepad: GamepadDevice,
+        input: Input,
+        dim: ActionValueDim,
+        aggregation: GamepadAggregation,
+        last_gamepad: Option<Entity>,
+    ) -> Option<ActionValue> {
+        if let Some(&value) = self.0.get(&(GamepadDevice::Any, input)) {
+            return Some(value.convert(dim));
+        }
+
+        if let GamepadDevice::Single(_) = gamepad {
+            return self
+                .0
+                .get(&(gamepad, input))
+                .map(|value| value.convert(dim));
+        }
+
+        let values: Vec<_> = self
+            .0
+            .iter()
+            .filter_map(
+                |(&(injected_gamepad, injected_input), &value)| match injected_gamepad {
+                    GamepadDevice::Single(entity)
+                        if injected_input == input && gamepad.matches(entity) =>
+                    {
+                        Some((entity, value.convert(dim)))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+
+        let value = if dim == ActionValueDim::Bool {
+            values.iter().any(|(_, value)| value.as_bool()).into()
+        } else {
+            let aggregate = |axis: fn(Vec3) -> f32| {
+                aggregation.aggregate(
+                    values
+                        .iter()
+                        .map(|&(entity, value)| (entity, axis(value.as_axis3d()))),
+                    last_gamepad,
+                )
+            };
+            let value = Vec3::new(aggregate(|v| v.x), aggregate(|v| v.y), aggregate(|v| v.z));
+            ActionValue::from(value).convert(dim)
+        };
+
+        value.as_bool().then_some(value)
+    }
+}
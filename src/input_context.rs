@@ -1,5 +1,7 @@
+pub mod clash_strategy;
 pub mod context_instance;
 pub mod events;
+pub mod gamepad_assignment;
 pub mod input_action;
 pub mod input_bind;
 pub mod input_condition;
@@ -14,8 +16,10 @@ use std::{
 
 use bevy::prelude::*;
 
-use crate::input::input_reader::InputReader;
+use crate::input::{input_reader::InputReader, GamepadDevice};
+use clash_strategy::ClashStrategy;
 use context_instance::ContextInstance;
+use gamepad_assignment::GamepadSlot;
 
 /// An extension trait for [`App`] to register contexts.
 ///
@@ -81,33 +85,36 @@ fn remove_instance<C: InputContext>(
 }
 
 /// Stores instantiated [`InputContext`]s.
+///
+/// Instances are evaluated in the order of their priority, see [`InputContext::PRIORITY`]
+/// and [`Self::set_priority`]. Instances with the same priority are evaluated in the order
+/// they were added. Layers pushed via [`Self::push_layer`] are evaluated before other instances of their entity.
 #[derive(Resource, Default)]
-pub struct ContextInstances(Vec<InstanceGroup>);
+pub struct ContextInstances {
+    groups: Vec<InstanceGroup>,
+    next_order: u64,
+}
 
 impl ContextInstances {
     fn add<C: InputContext>(&mut self, world: &World, entity: Entity) {
         debug!("adding `{}` to `{entity}`", any::type_name::<C>());
 
-        if let Some(index) = self.index::<C>() {
-            match &mut self.0[index] {
-                InstanceGroup::Exclusive { instances, .. } => {
-                    let ctx = C::context_instance(world, entity);
-                    instances.push((entity, ctx));
-                }
-                InstanceGroup::Shared { entities, .. } => {
-                    entities.push(entity);
-                }
+        if let ContextMode::Shared = C::MODE {
+            if let Some(group) = self
+                .groups
+                .iter_mut()
+                .find(|group| group.type_id == TypeId::of::<C>())
+            {
+                group.entities.push(entity);
+                return;
             }
-        } else {
-            let priority = Reverse(C::PRIORITY);
-            let index = self
-                .0
-                .binary_search_by_key(&priority, |group| Reverse(group.priority()))
-                .unwrap_or_else(|e| e);
-
-            let group = InstanceGroup::new::<C>(world, entity);
-            self.0.insert(index, group);
         }
+
+        let group = InstanceGroup::new::<C>(world, entity, self.next_order);
+        self.next_order += 1;
+
+        self.groups.push(group);
+        self.sort();
     }
 
     fn rebuild<C: InputContext>(
@@ -116,26 +123,23 @@ impl ContextInstances {
         time: &Time<Virtual>,
         commands: &mut Commands,
     ) {
-        if let Some(index) = self.index::<C>() {
+        for group in self
+            .groups
+            .iter_mut()
+            .filter(|group| group.type_id == TypeId::of::<C>())
+        {
             debug!("rebuilding `{}`", any::type_name::<C>());
 
-            match &mut self.0[index] {
-                InstanceGroup::Exclusive { instances, .. } => {
-                    for (entity, ctx) in instances {
-                        ctx.trigger_removed(commands, time, &[*entity]);
-                        *ctx = C::context_instance(world, *entity);
-                    }
-                }
-                InstanceGroup::Shared { ctx, entities, .. } => {
-                    ctx.trigger_removed(commands, time, entities);
-
-                    // For shared contexts rebuild the instance using the first entity.
-                    let entity = *entities
-                        .first()
-                        .expect("groups should be immediately removed when empty");
-                    *ctx = C::context_instance(world, entity);
-                }
-            }
+            group.ctx.trigger_removed(commands, time, &group.entities);
+
+            // For shared contexts rebuild the instance using the first entity.
+            let entity = *group
+                .entities
+                .first()
+                .expect("groups should be immediately removed when empty");
+            let enabled = group.ctx.is_enabled();
+            group.ctx = new_instance::<C>(world, entity);
+            group.ctx.set_enabled(enabled);
         }
     }
 
@@ -148,43 +152,56 @@ impl ContextInstances {
         debug!("removing `{}` from `{entity}`", any::type_name::<C>());
 
         let group_index = self
-            .index::<C>()
+            .index::<C>(entity)
             .expect("context should be instantiated before removal");
 
-        let empty = match &mut self.0[group_index] {
-            InstanceGroup::Exclusive { instances, .. } => {
-                let entity_index = instances
-                    .iter()
-                    .position(|&(mapped_entity, _)| mapped_entity == entity)
-                    .expect("entity should be inserted before removal");
-
-                let (_, ctx) = instances.swap_remove(entity_index);
-                ctx.trigger_removed(commands, time, &[entity]);
-
-                instances.is_empty()
-            }
-            InstanceGroup::Shared {
-                entities,
-                ctx: instance,
-                ..
-            } => {
-                let entity_index = entities
-                    .iter()
-                    .position(|&mapped_entity| mapped_entity == entity)
-                    .expect("entity should be inserted before removal");
-
-                entities.swap_remove(entity_index);
-                instance.trigger_removed(commands, time, &[entity]);
-
-                entities.is_empty()
-            }
-        };
+        let group = &mut self.groups[group_index];
+        let entity_index = group
+            .entities
+            .iter()
+            .position(|&mapped_entity| mapped_entity == entity)
+            .expect("entity should be inserted before removal");
+
+        group.entities.swap_remove(entity_index);
+        group.ctx.trigger_removed(commands, time, &[entity]);
+        if group.layer.is_some_and(|layer| layer.entity == entity) {
+            group.layer = None;
+            group.block_lower = false;
+        }
 
-        if empty {
+        if group.entities.is_empty() {
             // Remove the group if no entity references it.
             debug!("removing empty `{}`", any::type_name::<C>());
-            self.0.remove(group_index);
+            self.groups.remove(group_index);
+        }
+
+        self.sort();
+    }
+
+    /// Sorts groups in evaluation order.
+    ///
+    /// Layers are placed right before the first non-layer group of their entity,
+    /// so they don't affect the evaluation order relative to other entities.
+    fn sort(&mut self) {
+        for index in 0..self.groups.len() {
+            let Some(mut layer) = self.groups[index].layer else {
+                continue;
+            };
+
+            layer.anchor = self
+                .groups
+                .iter()
+                .filter(|group| group.layer.is_none() && group.entities.contains(&layer.entity))
+                .map(|group| (Reverse(group.priority), group.order))
+                .min()
+                .unwrap_or((
+                    Reverse(self.groups[index].priority),
+                    self.groups[index].order,
+                ));
+            self.groups[index].layer = Some(layer);
         }
+
+        self.groups.sort_by_key(InstanceGroup::sort_key);
     }
 
     pub(crate) fn update(
@@ -192,19 +209,255 @@ impl ContextInstances {
         commands: &mut Commands,
         reader: &mut InputReader,
         time: &Time<Virtual>,
+        strategy: ClashStrategy,
     ) {
-        for group in &mut self.0 {
-            match group {
-                InstanceGroup::Exclusive { instances, .. } => {
-                    for (entity, ctx) in instances {
-                        ctx.update(commands, reader, time, &[*entity]);
-                    }
+        self.resolve_clashes(reader, strategy);
+
+        let mut blocked = Vec::new();
+        for group in &mut self.groups {
+            reader.set_blocked(group.is_blocked(&blocked));
+            group.ctx.update(commands, reader, time, &group.entities);
+            if group.blocks_lower() {
+                blocked.extend_from_slice(&group.entities);
+            }
+        }
+        reader.set_blocked(false);
+    }
+
+    /// Blocks bindings that clash according to the strategy.
+    fn resolve_clashes(&mut self, reader: &mut InputReader, strategy: ClashStrategy) {
+        let mut chords = Vec::new();
+        if strategy != ClashStrategy::AllFire {
+            let mut blocked = Vec::new();
+            for group in &self.groups {
+                // Bindings of blocked groups can't clash since they read nothing.
+                if group.is_blocked(&blocked) {
+                    continue;
                 }
-                InstanceGroup::Shared { entities, ctx, .. } => {
-                    ctx.update(commands, reader, time, entities);
+
+                group.ctx.collect_chords(reader, &mut chords);
+                if group.blocks_lower() {
+                    blocked.extend_from_slice(&group.entities);
                 }
             }
         }
+
+        let mut clashed = strategy.resolve(&chords).into_iter();
+        for group in &mut self.groups {
+            group.ctx.set_clashed(&mut clashed);
+        }
+    }
+
+    /// Transitions all actions to [`ActionState::None`](context_instance::ActionState::None)
+    /// and ignores currently held inputs until they are released.
+    pub(crate) fn reset(&mut self, commands: &mut Commands, time: &Time<Virtual>) {
+        for group in &mut self.groups {
+            group.ctx.reset(commands, time, &group.entities);
+        }
+    }
+
+    /// Like [`Self::reset`], but only for contexts with [`InputContext::RESET_ON_FOCUS_LOSS`].
+    pub(crate) fn reset_on_focus_loss(&mut self, commands: &mut Commands, time: &Time<Virtual>) {
+        for group in self
+            .groups
+            .iter_mut()
+            .filter(|group| group.reset_on_focus_loss)
+        {
+            group.ctx.reset(commands, time, &group.entities);
+        }
+    }
+
+    /// Ignores currently held keyboard inputs in all instances until they are released.
+    ///
+    /// See also [`ContextInstance::ignore_held_keys`].
+    pub(crate) fn ignore_held_keys(&mut self) {
+        for group in &mut self.groups {
+            group.ctx.ignore_held_keys();
+        }
+    }
+
+    /// Associates all exclusive instances of the entity with the gamepad.
+    ///
+    /// Unlike [`RebuildInputContexts`], keeps the current action states.
+    /// The gamepad will be reset on rebuild, unless assigned via
+    /// [`GamepadSlot`].
+    ///
+    /// See also [`ContextInstance::set_gamepad`].
+    pub fn set_gamepad(&mut self, instance_entity: Entity, gamepad: impl Into<GamepadDevice>) {
+        let gamepad = gamepad.into();
+        for group in self.groups.iter_mut().filter(|group| {
+            group.mode == ContextMode::Exclusive && group.entities.contains(&instance_entity)
+        }) {
+            group.ctx.set_gamepad(gamepad);
+        }
+    }
+
+    /// Enables or disables the context instance of the entity without removing the component.
+    ///
+    /// For [`ContextMode::Shared`] affects all entities that share the instance.
+    /// Does nothing if the entity doesn't have the context.
+    ///
+    /// See [`ContextInstance::set_enabled`] for details.
+    pub fn set_enabled<C: InputContext>(&mut self, instance_entity: Entity, enabled: bool) {
+        debug!(
+            "setting `{}` enabled for `{instance_entity}` to `{enabled}`",
+            any::type_name::<C>()
+        );
+
+        if let Some(index) = self.index::<C>(instance_entity) {
+            self.groups[index].ctx.set_enabled(enabled);
+        }
+    }
+
+    /// Overrides the priority of the context instance of the entity.
+    ///
+    /// Unlike [`InputContext::PRIORITY`], it can be changed at runtime and affects only
+    /// the instance of the specified entity. For example, to prioritize the vehicle the player is focused on.
+    /// Pass [`InputContext::PRIORITY`] to restore the default. Preserved on rebuild.
+    ///
+    /// For [`ContextMode::Shared`] affects all entities that share the instance.
+    /// Does nothing if the entity doesn't have the context.
+    pub fn set_priority<C: InputContext>(&mut self, instance_entity: Entity, priority: isize) {
+        debug!(
+            "setting `{}` priority for `{instance_entity}` to `{priority}`",
+            any::type_name::<C>()
+        );
+
+        if let Some(index) = self.index::<C>(instance_entity) {
+            self.groups[index].priority = priority;
+            self.sort();
+        }
+    }
+
+    /// Returns the current priority of the context instance of the entity, if it exists.
+    ///
+    /// See also [`Self::set_priority`].
+    pub fn priority<C: InputContext>(&self, instance_entity: Entity) -> Option<isize> {
+        self.index::<C>(instance_entity)
+            .map(|index| self.groups[index].priority)
+    }
+
+    /// Pushes the context instance of the entity on top of the entity's layer stack.
+    ///
+    /// The pushed layer is evaluated before all other instances of the entity, including layers
+    /// pushed earlier, regardless of their priority. If `block_lower` is set, instances of the entity
+    /// evaluated after it read inputs as consumed. Instances of other entities aren't affected:
+    /// they are evaluated relative to the layer as if it was the entity's highest-priority instance.
+    /// Useful for modal menus and dialogs.
+    ///
+    /// Enables the instance if it was disabled. Inputs that are currently held
+    /// won't trigger its actions until released. If the instance is already in the stack,
+    /// it will be moved to the top.
+    ///
+    /// Does nothing if the entity doesn't have the context.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// fn open_pause(trigger: Trigger<Started<Pause>>, mut instances: ResMut<ContextInstances>) {
+    ///     instances.push_layer::<PauseMenu>(trigger.entity(), true);
+    /// }
+    ///
+    /// fn close_pause(trigger: Trigger<Started<Close>>, mut instances: ResMut<ContextInstances>) {
+    ///     instances.pop_layer(trigger.entity());
+    /// }
+    /// # #[derive(Component)]
+    /// # struct PauseMenu;
+    /// # impl InputContext for PauseMenu {
+    /// # fn context_instance(_world: &World, _entity: Entity) -> ContextInstance { Default::default() }
+    /// # }
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Pause;
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Close;
+    /// ```
+    pub fn push_layer<C: InputContext>(&mut self, instance_entity: Entity, block_lower: bool) {
+        debug!(
+            "pushing `{}` layer for `{instance_entity}` with blocking set to `{block_lower}`",
+            any::type_name::<C>()
+        );
+
+        let Some(index) = self.index::<C>(instance_entity) else {
+            return;
+        };
+
+        let depth = self
+            .layers(instance_entity)
+            .filter_map(|group| group.layer)
+            .map(|layer| layer.depth)
+            .max()
+            .map_or(0, |depth| depth + 1);
+
+        let group = &mut self.groups[index];
+        if group.layer.is_none() {
+            group.enabled_before_push = group.ctx.is_enabled();
+        }
+        group.layer = Some(Layer {
+            entity: instance_entity,
+            depth,
+            anchor: (Reverse(group.priority), group.order),
+        });
+        group.block_lower = block_lower;
+        group.ctx.set_enabled(true);
+        group.ctx.ignore_held_inputs();
+
+        self.sort();
+    }
+
+    /// Pops the top layer from the entity's layer stack.
+    ///
+    /// The popped instance is restored to the enabled state it had before [`Self::push_layer`]
+    /// and the previous layer becomes the top again.
+    /// Inputs that are currently held won't trigger actions of the remaining instances until released,
+    /// so the input that closed the layer won't be picked up by the layers below.
+    ///
+    /// Returns `false` if the stack is empty.
+    ///
+    /// See also [`Self::push_layer`].
+    pub fn pop_layer(&mut self, instance_entity: Entity) -> bool {
+        // Layers of the entity are sorted from the top, so the first one is the last pushed.
+        let Some(group) = self.groups.iter_mut().find(|group| {
+            group
+                .layer
+                .is_some_and(|layer| layer.entity == instance_entity)
+        }) else {
+            return false;
+        };
+
+        debug!("popping layer for `{instance_entity}`");
+
+        group.layer = None;
+        group.block_lower = false;
+        group.ctx.set_enabled(group.enabled_before_push);
+
+        for group in self
+            .groups
+            .iter_mut()
+            .filter(|group| group.entities.contains(&instance_entity))
+        {
+            group.ctx.ignore_held_inputs();
+        }
+
+        self.sort();
+
+        true
+    }
+
+    /// Returns the number of layers in the entity's layer stack.
+    ///
+    /// See also [`Self::push_layer`].
+    pub fn layers_count(&self, instance_entity: Entity) -> usize {
+        self.layers(instance_entity).count()
+    }
+
+    fn layers(&self, instance_entity: Entity) -> impl Iterator<Item = &InstanceGroup> {
+        self.groups.iter().filter(move |group| {
+            group
+                .layer
+                .is_some_and(|layer| layer.entity == instance_entity)
+        })
     }
 
     /// Returns a context instance for an entity, if it exists.
@@ -212,7 +465,7 @@ impl ContextInstances {
     /// For a more ergonomic API, it's recommended to react on [`events`].
     /// within observers.
     ///
-    /// The complexity is `O(n+m)`, where `n` is the number of contexts and `m` is the number of entities,
+    /// The complexity is `O(n)`, where `n` is the number of instances,
     /// since the storage is optimized for iteration. However, there are usually only a few contexts that are instantiated.
     ///
     /// ```
@@ -237,78 +490,102 @@ impl ContextInstances {
     /// # struct Dodge;
     /// ```
     pub fn get<C: InputContext>(&self, instance_entity: Entity) -> Option<&ContextInstance> {
-        let index = self.index::<C>()?;
-        match &self.0[index] {
-            InstanceGroup::Exclusive { instances, .. } => {
-                instances.iter().find_map(|(entity, ctx)| {
-                    if *entity == instance_entity {
-                        Some(ctx)
-                    } else {
-                        None
-                    }
-                })
-            }
-            InstanceGroup::Shared { entities, ctx, .. } => {
-                entities.contains(&instance_entity).then_some(ctx)
-            }
-        }
+        self.index::<C>(instance_entity)
+            .map(|index| &self.groups[index].ctx)
     }
 
-    fn index<C: InputContext>(&self) -> Option<usize> {
-        self.0
-            .iter()
-            .position(|group| group.type_id() == TypeId::of::<C>())
+    fn index<C: InputContext>(&self, instance_entity: Entity) -> Option<usize> {
+        self.groups.iter().position(|group| {
+            group.type_id == TypeId::of::<C>() && group.entities.contains(&instance_entity)
+        })
     }
 }
 
-/// Instances of [`InputContext`] for the same type based on [`InputContext::MODE`].
-enum InstanceGroup {
-    Exclusive {
-        type_id: TypeId,
-        priority: isize,
-        instances: Vec<(Entity, ContextInstance)>,
-    },
-    Shared {
-        type_id: TypeId,
-        priority: isize,
-        entities: Vec<Entity>,
-        ctx: ContextInstance,
-    },
+/// Instance of [`InputContext`] with associated entities.
+///
+/// For [`ContextMode::Exclusive`] there is always a single entity.
+struct InstanceGroup {
+    type_id: TypeId,
+    mode: ContextMode,
+    priority: isize,
+    /// Sequence number of the group creation used to break priority ties.
+    order: u64,
+    /// Position in the entity's layer stack, see [`ContextInstances::push_layer`].
+    layer: Option<Layer>,
+    block_lower: bool,
+    /// Enabled state to restore on [`ContextInstances::pop_layer`].
+    enabled_before_push: bool,
+    reset_on_focus_loss: bool,
+    entities: Vec<Entity>,
+    ctx: ContextInstance,
 }
 
 impl InstanceGroup {
     #[must_use]
-    fn new<C: InputContext>(world: &World, entity: Entity) -> Self {
-        let type_id = TypeId::of::<C>();
-        let ctx = C::context_instance(world, entity);
-        match C::MODE {
-            ContextMode::Exclusive => Self::Exclusive {
-                type_id,
-                priority: C::PRIORITY,
-                instances: vec![(entity, ctx)],
-            },
-            ContextMode::Shared => Self::Shared {
-                type_id,
-                priority: C::PRIORITY,
-                entities: vec![entity],
-                ctx,
-            },
+    fn new<C: InputContext>(world: &World, entity: Entity, order: u64) -> Self {
+        Self {
+            type_id: TypeId::of::<C>(),
+            mode: C::MODE,
+            priority: C::PRIORITY,
+            order,
+            layer: None,
+            block_lower: false,
+            enabled_before_push: false,
+            reset_on_focus_loss: C::RESET_ON_FOCUS_LOSS,
+            entities: vec![entity],
+            ctx: new_instance::<C>(world, entity),
         }
     }
 
-    fn priority(&self) -> isize {
-        match *self {
-            InstanceGroup::Exclusive { priority, .. } => priority,
-            InstanceGroup::Shared { priority, .. } => priority,
-        }
+    /// Returns the key by which groups are sorted in evaluation order.
+    fn sort_key(&self) -> (Reverse<isize>, u64, Reverse<Option<usize>>) {
+        let (priority, order) = self
+            .layer
+            .map_or((Reverse(self.priority), self.order), |layer| layer.anchor);
+
+        // Layers go before their anchor group since `Reverse(None)` is the greatest.
+        (
+            priority,
+            order,
+            Reverse(self.layer.map(|layer| layer.depth)),
+        )
+    }
+
+    /// Returns `true` if instances of the same entities evaluated after this group
+    /// should read inputs as consumed.
+    fn blocks_lower(&self) -> bool {
+        self.block_lower && self.ctx.is_enabled()
     }
 
-    fn type_id(&self) -> TypeId {
-        match *self {
-            InstanceGroup::Exclusive { type_id, .. } => type_id,
-            InstanceGroup::Shared { type_id, .. } => type_id,
+    /// Returns `true` if any of the group entities is blocked by a layer evaluated earlier.
+    fn is_blocked(&self, blocked: &[Entity]) -> bool {
+        self.entities.iter().any(|entity| blocked.contains(entity))
+    }
+}
+
+/// Position of [`InstanceGroup`] in the entity's layer stack.
+#[derive(Clone, Copy)]
+struct Layer {
+    entity: Entity,
+    depth: usize,
+    /// Sort key of the first non-layer group of the entity.
+    ///
+    /// Updated by [`ContextInstances::sort`] to evaluate the layer right before it.
+    anchor: (Reverse<isize>, u64),
+}
+
+/// Creates an instance and applies settings from the context.
+///
+/// For [`ContextMode::Exclusive`] also applies the gamepad from [`GamepadSlot`].
+fn new_instance<C: InputContext>(world: &World, entity: Entity) -> ContextInstance {
+    let mut ctx = C::context_instance(world, entity);
+    ctx.ignore_ui_focus = C::IGNORE_UI_FOCUS;
+    if let ContextMode::Exclusive = C::MODE {
+        if let Some(slot) = world.get::<GamepadSlot>(entity) {
+            ctx.set_gamepad(slot.device());
         }
     }
+    ctx
 }
 
 /// Contexts are components that associate entities with [`InputAction`](input_action::InputAction)s.
@@ -318,6 +595,7 @@ impl InstanceGroup {
 ///
 /// Removing deactivates [`ContextInstance`] for the entity and trigger
 /// transitions for all actions to [`ActionState::None`](crate::input_context::context_instance::ActionState::None).
+/// To pause the context without rebuilding it on re-insertion, use [`ContextInstances::set_enabled`].
 ///
 /// Each context should be registered using [`ContextAppExt::add_input_context`].
 ///
@@ -364,8 +642,25 @@ pub trait InputContext: Component {
     ///
     /// Ordering is global.
     /// Contexts with a higher priority evaluated first.
+    /// Can be overridden per entity at runtime via [`ContextInstances::set_priority`].
+    /// Layers pushed via [`ContextInstances::push_layer`] are always evaluated before
+    /// other instances of their entity.
     const PRIORITY: isize = 0;
 
+    /// Whether to release all actions when the window loses focus.
+    ///
+    /// If enabled, all actions transition to [`ActionState::None`](context_instance::ActionState::None)
+    /// and currently held inputs are ignored until released.
+    /// This prevents actions from staying active or immediately re-triggering when focus returns.
+    const RESET_ON_FOCUS_LOSS: bool = true;
+
+    /// Whether actions of this context should read inputs owned by UI.
+    ///
+    /// Can also be configured per action with
+    /// [`InputAction::IGNORE_UI_FOCUS`](input_action::InputAction::IGNORE_UI_FOCUS).
+    /// See [`InputFocus`](crate::input::input_focus::InputFocus) for details.
+    const IGNORE_UI_FOCUS: bool = false;
+
     /// Creates a new instance for the given entity.
     ///
     /// In the implementation you need call [`ContextInstance::bind`]
@@ -379,7 +674,7 @@ pub trait InputContext: Component {
 }
 
 /// Configures how instances of [`InputContext`] will be managed.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContextMode {
     /// Instantiate a new context for each entity.
     ///
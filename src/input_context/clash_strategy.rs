@@ -0,0 +1,268 @@
+use bevy::prelude::*;
+
+use crate::input::{input_combo::ComboButton, Input, ModKeys};
+
+/// Strategy for resolving clashes between active bindings.
+///
+/// Two bindings clash when all buttons of one binding are also held for the other,
+/// for example `KeyS` and `Ctrl+KeyS` or `GamepadButton::South` and `LeftTrigger+Gamepad:South`.
+/// Keyboard modifiers are compared regardless of side, so `ControlLeft` clashes with `Ctrl+KeyS`.
+///
+/// Clashes are resolved across all contexts and devices before evaluating actions.
+/// Blocked bindings read zero for the current frame.
+/// Gamepad buttons clash only if they are pressed on the same gamepad.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(ClashStrategy::PreferLargest);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ClashStrategy {
+    /// All clashing bindings are evaluated.
+    ///
+    /// Can be controlled manually via [`InputContext::PRIORITY`](super::InputContext::PRIORITY)
+    /// and [`InputAction::CONSUME_INPUT`](super::input_action::InputAction::CONSUME_INPUT).
+    #[default]
+    AllFire,
+    /// Only bindings with the largest set of buttons are evaluated.
+    ///
+    /// For example, pressing `Ctrl+S` blocks `KeyS`, but not `Ctrl+KeyS`.
+    PreferLargest,
+    /// Only the first binding in evaluation order is evaluated.
+    ///
+    /// Contexts are evaluated by their priority and bindings in the order they are bound.
+    FirstWins,
+}
+
+impl ClashStrategy {
+    /// Returns which chords should be blocked.
+    ///
+    /// [`None`] represents inactive bindings that are never blocked.
+    pub(super) fn resolve(self, chords: &[Option<Chord>]) -> Vec<bool> {
+        chords
+            .iter()
+            .enumerate()
+            .map(|(index, chord)| {
+                let Some(chord) = chord else {
+                    return false;
+                };
+
+                match self {
+                    ClashStrategy::AllFire => false,
+                    ClashStrategy::PreferLargest => chords
+                        .iter()
+                        .flatten()
+                        .any(|other| chord.len() < other.len() && chord.is_subset(other)),
+                    ClashStrategy::FirstWins => chords[..index]
+                        .iter()
+                        .flatten()
+                        .any(|other| chord.is_subset(other) || other.is_subset(chord)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// All buttons that need to be held for an input.
+#[derive(Debug)]
+pub(super) struct Chord(Vec<ChordPart>);
+
+impl Chord {
+    /// Decomposes the input into parts.
+    ///
+    /// `gamepad` is the entity that provides the value for gamepad inputs.
+    pub(super) fn new(input: Input, gamepad: Option<Entity>) -> Self {
+        let mut parts = Vec::new();
+        match input {
+            Input::Keyboard { key, mod_keys } => {
+                push_key(&mut parts, key);
+                push_mod_keys(&mut parts, mod_keys);
+            }
+            Input::MouseButton { button, mod_keys } => {
+                parts.push(ChordPart::MouseButton(button));
+                push_mod_keys(&mut parts, mod_keys);
+            }
+            Input::MouseMotion { mod_keys } | Input::MouseMotionDirection { mod_keys, .. } => {
+                parts.push(ChordPart::Other(Input::mouse_motion()));
+                push_mod_keys(&mut parts, mod_keys);
+            }
+            Input::MouseWheel { mod_keys } | Input::MouseWheelDirection { mod_keys, .. } => {
+                parts.push(ChordPart::Other(Input::mouse_wheel()));
+                push_mod_keys(&mut parts, mod_keys);
+            }
+            Input::GamepadButton {
+                button,
+                mod_buttons,
+            }
+            | Input::GamepadAnalog {
+                button,
+                mod_buttons,
+            } => {
+                parts.push(ChordPart::GamepadButton(button, gamepad));
+                for button in mod_buttons.iter_buttons() {
+                    parts.push(ChordPart::GamepadButton(button, gamepad));
+                }
+            }
+            Input::GamepadAxis { axis, mod_buttons } => {
+                parts.push(ChordPart::GamepadAxis(axis, gamepad));
+                for button in mod_buttons.iter_buttons() {
+                    parts.push(ChordPart::GamepadButton(button, gamepad));
+                }
+            }
+            Input::Combo(combo) => {
+                for button in combo.iter() {
+                    match button {
+                        ComboButton::Keyboard(key) => push_key(&mut parts, key),
+                        ComboButton::MouseButton(button) => {
+                            parts.push(ChordPart::MouseButton(button))
+                        }
+                        ComboButton::GamepadButton(button) => {
+                            parts.push(ChordPart::GamepadButton(button, gamepad))
+                        }
+                    }
+                }
+            }
+            Input::Touch { .. }
+            | Input::TouchDrag
+            | Input::TouchPinch
+            | Input::TouchPan
+            | Input::Custom(_)
+            | Input::EntityPress { .. } => parts.push(ChordPart::Other(input)),
+        }
+
+        // Modifiers could be specified twice, e.g. `Ctrl+ControlLeft`.
+        let mut unique = Vec::with_capacity(parts.len());
+        for part in parts {
+            if !unique.contains(&part) {
+                unique.push(part);
+            }
+        }
+
+        Self(unique)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_subset(&self, other: &Chord) -> bool {
+        self.0.iter().all(|part| other.0.contains(part))
+    }
+}
+
+/// Keys that are modifiers are stored as [`ModKeys`] to match both sides.
+fn push_key(parts: &mut Vec<ChordPart>, key: KeyCode) {
+    let mod_keys = ModKeys::from(key);
+    if mod_keys.is_empty() {
+        parts.push(ChordPart::Key(key));
+    } else {
+        push_mod_keys(parts, mod_keys);
+    }
+}
+
+fn push_mod_keys(parts: &mut Vec<ChordPart>, mod_keys: ModKeys) {
+    for mod_key in mod_keys.without_sides().iter() {
+        parts.push(ChordPart::ModKey(mod_key));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChordPart {
+    Key(KeyCode),
+    ModKey(ModKeys),
+    MouseButton(MouseButton),
+    GamepadButton(GamepadButton, Option<Entity>),
+    GamepadAxis(GamepadAxis, Option<Entity>),
+    Other(Input),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{input_combo::InputCombo, GamepadModButtons, InputModButtons, InputModKeys};
+
+    #[test]
+    fn prefer_largest() {
+        let chords = [
+            Some(keyboard_chord(KeyCode::KeyS)),
+            Some(keyboard_chord(
+                KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL),
+            )),
+            Some(keyboard_chord(KeyCode::ControlRight)),
+            Some(keyboard_chord(KeyCode::KeyD)),
+            None,
+        ];
+        assert_eq!(
+            ClashStrategy::PreferLargest.resolve(&chords),
+            [true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn first_wins() {
+        let chords = [
+            None,
+            Some(keyboard_chord(KeyCode::KeyS)),
+            Some(keyboard_chord(
+                KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL),
+            )),
+            Some(keyboard_chord(KeyCode::KeyS)),
+            Some(keyboard_chord(KeyCode::KeyD)),
+        ];
+        assert_eq!(
+            ClashStrategy::FirstWins.resolve(&chords),
+            [false, false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn all_fire() {
+        let chords = [
+            Some(keyboard_chord(KeyCode::KeyS)),
+            Some(keyboard_chord(
+                KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL),
+            )),
+        ];
+        assert_eq!(ClashStrategy::AllFire.resolve(&chords), [false, false]);
+    }
+
+    #[test]
+    fn gamepad() {
+        let gamepad1 = Entity::from_raw(1);
+        let gamepad2 = Entity::from_raw(2);
+        let button = GamepadButton::South;
+        let with_modifier = button.with_mod_buttons(GamepadModButtons::LEFT_TRIGGER);
+        let chords = [
+            Some(Chord::new(button.into(), Some(gamepad1))),
+            Some(Chord::new(button.into(), Some(gamepad2))),
+            Some(Chord::new(with_modifier, Some(gamepad1))),
+        ];
+        assert_eq!(
+            ClashStrategy::PreferLargest.resolve(&chords),
+            [true, false, false],
+            "buttons should clash only on the same gamepad"
+        );
+    }
+
+    #[test]
+    fn combo() {
+        let combo = InputCombo::default()
+            .with(KeyCode::KeyG)
+            .with(MouseButton::Left);
+        let chords = [
+            Some(keyboard_chord(KeyCode::KeyG)),
+            Some(Chord::new(MouseButton::Left.into(), None)),
+            Some(Chord::new(combo.into(), None)),
+        ];
+        assert_eq!(
+            ClashStrategy::PreferLargest.resolve(&chords),
+            [true, true, false]
+        );
+    }
+
+    fn keyboard_chord(input: impl Into<Input>) -> Chord {
+        Chord::new(input.into(), None)
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     any::{self, TypeId},
     cmp::Ordering,
     fmt::Debug,
+    ops::Deref,
 };
 
 use bevy::{
@@ -12,6 +13,7 @@ use bevy::{
 };
 
 use super::{
+    clash_strategy::Chord,
     events::{ActionEvents, Canceled, Completed, Fired, Ongoing, Started},
     input_action::{Accumulation, ActionOutput, InputAction},
     input_bind::{InputBind, InputBindings},
@@ -20,7 +22,11 @@ use super::{
 };
 use crate::{
     action_value::{ActionValue, ActionValueDim},
-    input::{input_reader::InputReader, GamepadDevice, Input},
+    input::{
+        input_reader::InputReader,
+        last_input_device::{self, InputDevice, InputDeviceChanged},
+        GamepadDevice, Input,
+    },
 };
 use trigger_tracker::TriggerTracker;
 
@@ -49,10 +55,16 @@ use trigger_tracker::TriggerTracker;
 /// This prevents unintended behavior where switching contexts using the same key
 /// could cause an immediate switch back, as buttons are rarely pressed for only a single frame.
 ///
+/// Instances can be paused without removing the context component via [`Self::set_enabled`]
+/// or [`ContextInstances::set_enabled`](super::ContextInstances::set_enabled).
+///
 /// [`ActionState`]: super::context_instance::ActionState
 #[derive(Default)]
 pub struct ContextInstance {
     gamepad: GamepadDevice,
+    pub(super) ignore_ui_focus: bool,
+    activity: Activity,
+    last_device: Option<InputDevice>,
     bindings: Vec<ActionBind>,
     actions: ActionsData,
 }
@@ -65,6 +77,37 @@ impl ContextInstance {
         self.gamepad = gamepad.into();
     }
 
+    /// Enables or disables evaluation of this instance.
+    ///
+    /// On the next update, disabled instance transitions all actions to [`ActionState::None`]
+    /// and triggers the corresponding events. Bindings with their modifiers and conditions
+    /// are kept, so the instance resumes from the same configuration.
+    /// Inputs held when the instance is enabled again will be ignored until released.
+    ///
+    /// Disabled instances don't read or consume inputs.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.activity = match (self.activity, enabled) {
+            (Activity::Enabled, false) => Activity::Disabling,
+            (Activity::Disabling | Activity::Disabled, true) => Activity::Enabled,
+            (activity, _) => activity,
+        };
+    }
+
+    /// Returns `true` if the instance is evaluated.
+    ///
+    /// See also [`Self::set_enabled`].
+    pub fn is_enabled(&self) -> bool {
+        self.activity == Activity::Enabled
+    }
+
+    /// Returns the last used device that matches the associated gamepad.
+    ///
+    /// [`InputDeviceChanged`] is triggered for context entities when it changes.
+    /// See also [`LastInputDevice`](crate::input::last_input_device::LastInputDevice).
+    pub fn last_device(&self) -> Option<InputDevice> {
+        self.last_device
+    }
+
     /// Starts binding an action.
     ///
     /// This method can be called multiple times for the same action to extend its mappings.
@@ -91,6 +134,16 @@ impl ContextInstance {
         self.actions.action::<A>()
     }
 
+    /// Returns bindings for action `A`.
+    ///
+    /// Useful for displaying bound inputs in on-screen prompts.
+    pub fn action_bind<A: InputAction>(&self) -> Option<&ActionBind> {
+        let type_id = TypeId::of::<A>();
+        self.bindings
+            .iter()
+            .find(|binding| binding.type_id == type_id)
+    }
+
     pub(super) fn update(
         &mut self,
         commands: &mut Commands,
@@ -98,12 +151,125 @@ impl ContextInstance {
         time: &Time<Virtual>,
         entities: &[Entity],
     ) {
+        match self.activity {
+            Activity::Enabled => (),
+            Activity::Disabling => {
+                self.reset(commands, time, entities);
+                self.activity = Activity::Disabled;
+                return;
+            }
+            Activity::Disabled => return,
+        }
+
+        let actuated = reader
+            .actuated_devices()
+            .iter()
+            .copied()
+            .filter(|device| device.matches(self.gamepad));
+        let device = last_input_device::next_device(self.last_device, actuated);
+        if device != self.last_device {
+            self.last_device = device;
+            if let Some(device) = device {
+                trigger_for_each(commands, entities, InputDeviceChanged { device });
+            }
+        }
+
         reader.set_gamepad(self.gamepad);
         for binding in &mut self.bindings {
+            reader.set_ignore_ui_focus(self.ignore_ui_focus || binding.ignore_ui_focus);
+            reader.set_active_in_text_input(binding.active_in_text_input);
             binding.update(commands, reader, &mut self.actions, time, entities);
         }
     }
 
+    /// Appends a chord for each binding, or [`None`] if the binding is inactive.
+    pub(super) fn collect_chords(&self, reader: &mut InputReader, chords: &mut Vec<Option<Chord>>) {
+        reader.set_gamepad(self.gamepad);
+        for action_bind in &self.bindings {
+            reader.set_ignore_ui_focus(self.ignore_ui_focus || action_bind.ignore_ui_focus);
+            reader.set_active_in_text_input(action_bind.active_in_text_input);
+            for binding in &action_bind.bindings {
+                let active = self.is_enabled()
+                    && !binding.ignored
+                    && !binding.has_extra_mod_keys(reader)
+                    && binding.read(reader).as_bool();
+                let chord =
+                    active.then(|| Chord::new(binding.input, reader.gamepad_entity(binding.input)));
+                chords.push(chord);
+            }
+        }
+    }
+
+    /// Marks bindings as clashed in the same order as [`Self::collect_chords`].
+    pub(super) fn set_clashed(&mut self, clashed: &mut impl Iterator<Item = bool>) {
+        for binding in self
+            .bindings
+            .iter_mut()
+            .flat_map(|binding| &mut binding.bindings)
+        {
+            binding.clashed = clashed.next().unwrap_or_default();
+        }
+    }
+
+    /// Transitions all actions to [`ActionState::None`] with zero value and triggers the corresponding events.
+    ///
+    /// Currently held inputs will be ignored until released.
+    pub(super) fn reset(
+        &mut self,
+        commands: &mut Commands,
+        time: &Time<Virtual>,
+        entities: &[Entity],
+    ) {
+        for binding in &mut self.bindings {
+            let action = self
+                .actions
+                .get_mut(&binding.type_id)
+                .expect("actions and bindings should have matching type IDs");
+            action.update(time, ActionState::None, ActionValue::zero(binding.dim));
+            action.trigger_events(commands, entities);
+
+            for input_bind in &mut binding.bindings {
+                input_bind.ignored = true;
+            }
+        }
+    }
+
+    /// Ignores currently held keyboard inputs until they are released.
+    ///
+    /// Skips actions with [`InputAction::ACTIVE_IN_TEXT_INPUT`].
+    pub(super) fn ignore_held_keys(&mut self) {
+        for binding in self
+            .bindings
+            .iter_mut()
+            .filter(|binding| !binding.active_in_text_input)
+        {
+            for input_bind in &mut binding.bindings {
+                if input_bind.input.uses_keyboard() {
+                    input_bind.ignored = true;
+                }
+            }
+        }
+    }
+
+    /// Ignores currently held inputs until they are released.
+    ///
+    /// Skips actions that aren't in [`ActionState::None`] to avoid interrupting them.
+    pub(super) fn ignore_held_inputs(&mut self) {
+        for binding in &mut self.bindings {
+            let action = self
+                .actions
+                .get(&binding.type_id)
+                .expect("actions and bindings should have matching type IDs");
+            if action.state() != ActionState::None {
+                continue;
+            }
+
+            for input_bind in &mut binding.bindings {
+                input_bind.ignored = true;
+            }
+        }
+    }
+
     /// Copies [`ActionData`] for each binding and triggers transition to [`ActionState::None`] with zero value.
     ///
     /// Instance data remains unchanges.
@@ -114,9 +280,10 @@ impl ContextInstance {
         entities: &[Entity],
     ) {
         for binding in &self.bindings {
-            let mut action = *self
+            let mut action = self
                 .actions
                 .get(&binding.type_id)
+                .copied()
                 .expect("actions and bindings should have matching type IDs");
             action.update(time, ActionState::None, ActionValue::zero(binding.dim));
             action.trigger_events(commands, entities);
@@ -124,6 +291,16 @@ impl ContextInstance {
     }
 }
 
+/// Evaluation state of [`ContextInstance`].
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+enum Activity {
+    #[default]
+    Enabled,
+    /// Disabled, but actions haven't been reset yet.
+    Disabling,
+    Disabled,
+}
+
 /// Bindings of [`InputAction`] for [`ContextInstance`].
 ///
 /// These bindings are stored separately from [`ActionsData`] to allow a currently
@@ -133,14 +310,16 @@ pub struct ActionBind {
     action_name: &'static str,
     consume_input: bool,
     accumulation: Accumulation,
+    ignore_ui_focus: bool,
+    active_in_text_input: bool,
     dim: ActionValueDim,
 
     modifiers: Vec<Box<dyn InputModifier>>,
     conditions: Vec<Box<dyn InputCondition>>,
     bindings: Vec<InputBind>,
 
-    /// Consumed inputs during state evaluation.
-    consume_buffer: Vec<Input>,
+    /// Inputs that contributed to the state during evaluation.
+    contributing: Vec<Input>,
 }
 
 impl ActionBind {
@@ -152,10 +331,12 @@ impl ActionBind {
             dim: A::Output::DIM,
             consume_input: A::CONSUME_INPUT,
             accumulation: A::ACCUMULATION,
+            ignore_ui_focus: A::IGNORE_UI_FOCUS,
+            active_in_text_input: A::ACTIVE_IN_TEXT_INPUT,
             modifiers: Default::default(),
             conditions: Default::default(),
             bindings: Default::default(),
-            consume_buffer: Default::default(),
+            contributing: Default::default(),
         }
     }
 
@@ -345,6 +526,13 @@ impl ActionBind {
         self
     }
 
+    /// Returns an iterator over inputs bound to this action in the order they were added.
+    ///
+    /// See also [`Input::prompt`].
+    pub fn inputs(&self) -> impl Iterator<Item = Input> + '_ {
+        self.bindings.iter().map(|binding| binding.input)
+    }
+
     fn update(
         &mut self,
         commands: &mut Commands,
@@ -357,7 +545,7 @@ impl ActionBind {
 
         let mut tracker = TriggerTracker::new(ActionValue::zero(self.dim));
         for binding in &mut self.bindings {
-            let value = reader.value(binding.input);
+            let value = binding.read(reader);
             if binding.ignored {
                 // Ignore until we read zero for this mapping.
                 if value.as_bool() {
@@ -367,6 +555,12 @@ impl ActionBind {
                 }
             }
 
+            let value = if binding.clashed || binding.has_extra_mod_keys(reader) {
+                ActionValue::zero(value.dim())
+            } else {
+                value
+            };
+
             let mut current_tracker = TriggerTracker::new(value);
             current_tracker.apply_modifiers(actions, time, &mut binding.modifiers);
             current_tracker.apply_conditions(actions, time, &mut binding.conditions);
@@ -383,16 +577,12 @@ impl ActionBind {
                 Ordering::Less => (),
                 Ordering::Equal => {
                     tracker.combine(current_tracker, self.accumulation);
-                    if self.consume_input {
-                        self.consume_buffer.push(binding.input);
-                    }
+                    self.contributing.push(binding.input);
                 }
                 Ordering::Greater => {
                     tracker.overwrite(current_tracker);
-                    if self.consume_input {
-                        self.consume_buffer.clear();
-                        self.consume_buffer.push(binding.input);
-                    }
+                    self.contributing.clear();
+                    self.contributing.push(binding.input);
                 }
             }
         }
@@ -407,14 +597,20 @@ impl ActionBind {
         let state = tracker.state();
         let value = tracker.value().convert(self.dim);
 
-        if self.consume_input {
-            if state != ActionState::None {
-                for &input in &self.consume_buffer {
+        if state != ActionState::None {
+            action.inputs = ActionInputs::from_slice(&self.contributing);
+            action.gamepad = self
+                .contributing
+                .iter()
+                .find_map(|&input| reader.gamepad_entity(input));
+
+            if self.consume_input {
+                for &input in &self.contributing {
                     reader.consume(input);
                 }
             }
-            self.consume_buffer.clear();
         }
+        self.contributing.clear();
 
         action.update(time, state, value);
         if !tracker.events_blocked() {
@@ -452,6 +648,8 @@ pub struct ActionData {
     state: ActionState,
     events: ActionEvents,
     value: ActionValue,
+    inputs: ActionInputs,
+    gamepad: Option<Entity>,
     elapsed_secs: f32,
     fired_secs: f32,
     trigger_events: fn(&Self, &mut Commands, &[Entity]),
@@ -467,6 +665,8 @@ impl ActionData {
             state: Default::default(),
             events: ActionEvents::empty(),
             value: ActionValue::zero(A::Output::DIM),
+            inputs: Default::default(),
+            gamepad: None,
             elapsed_secs: 0.0,
             fired_secs: 0.0,
             trigger_events: Self::trigger_events_typed::<A>,
@@ -518,6 +718,8 @@ impl ActionData {
                         Started::<A> {
                             value: A::Output::as_output(self.value),
                             state: self.state,
+                            inputs: self.inputs,
+                            gamepad: self.gamepad,
                         },
                     );
                 }
@@ -539,6 +741,8 @@ impl ActionData {
                         Fired::<A> {
                             value: A::Output::as_output(self.value),
                             state: self.state,
+                            inputs: self.inputs,
+                            gamepad: self.gamepad,
                             fired_secs: self.fired_secs,
                             elapsed_secs: self.elapsed_secs,
                         },
@@ -562,6 +766,8 @@ impl ActionData {
                         Completed::<A> {
                             value: A::Output::as_output(self.value),
                             state: self.state,
+                            inputs: self.inputs,
+                            gamepad: self.gamepad,
                             fired_secs: self.fired_secs,
                             elapsed_secs: self.elapsed_secs,
                         },
@@ -587,6 +793,29 @@ impl ActionData {
         self.value
     }
 
+    /// Returns inputs that drove the action during the last evaluation with a state other than [`ActionState::None`].
+    ///
+    /// Contains multiple inputs if they had the same state and were combined according to
+    /// [`InputAction::ACCUMULATION`]. Preserved after the action transitions to [`ActionState::None`],
+    /// so it's available in [`Completed`] and [`Canceled`].
+    ///
+    /// Holds up to [`ActionInputs::CAPACITY`] inputs, see [`ActionInputs`] for details.
+    pub fn inputs(&self) -> &[Input] {
+        &self.inputs
+    }
+
+    /// Returns the first input from [`Self::inputs`].
+    pub fn input(&self) -> Option<Input> {
+        self.inputs.first().copied()
+    }
+
+    /// Returns the gamepad that provided one of [`Self::inputs`].
+    ///
+    /// Useful when the context uses [`GamepadDevice::Any`].
+    pub fn gamepad(&self) -> Option<Entity> {
+        self.gamepad
+    }
+
     /// Time the action was in [`ActionState::Ongoing`] and [`ActionState::Fired`] states.
     pub fn elapsed_secs(&self) -> f32 {
         self.elapsed_secs
@@ -598,6 +827,62 @@ impl ActionData {
     }
 }
 
+/// Inputs that drove an action, stored inline to keep [`ActionData`] and events [`Copy`].
+///
+/// Can hold up to [`Self::CAPACITY`] inputs. If more inputs contributed, the rest are
+/// dropped. Dereferences to a slice of the stored inputs.
+#[derive(Clone, Copy)]
+pub struct ActionInputs {
+    inputs: [Input; Self::CAPACITY],
+    len: usize,
+}
+
+impl ActionInputs {
+    /// Maximum number of stored inputs.
+    pub const CAPACITY: usize = 8;
+
+    fn from_slice(inputs: &[Input]) -> Self {
+        let mut action_inputs = Self::default();
+        for (slot, &input) in action_inputs.inputs.iter_mut().zip(inputs) {
+            *slot = input;
+            action_inputs.len += 1;
+        }
+        action_inputs
+    }
+}
+
+impl Default for ActionInputs {
+    fn default() -> Self {
+        Self {
+            // Placeholder for unused slots, never exposed.
+            inputs: [Input::TouchDrag; Self::CAPACITY],
+            len: 0,
+        }
+    }
+}
+
+impl Deref for ActionInputs {
+    type Target = [Input];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inputs[..self.len]
+    }
+}
+
+impl PartialEq for ActionInputs {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for ActionInputs {}
+
+impl Debug for ActionInputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
 /// Triggers a copyable event for each entity separately and logs it.
 ///
 // It's cheaper to copy the event than to clone the entities.
@@ -648,6 +933,26 @@ mod tests {
         assert_eq!(action.bindings.len(), 2);
     }
 
+    #[test]
+    fn inputs() {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<DummyAction>()
+            .to((KeyCode::KeyA, GamepadButton::South));
+
+        let inputs: Vec<_> = ctx.action_bind::<DummyAction>().unwrap().inputs().collect();
+        assert_eq!(inputs, [KeyCode::KeyA.into(), GamepadButton::South.into()]);
+    }
+
+    #[test]
+    fn action_inputs_overflow() {
+        let inputs: Vec<_> = (0..=ActionInputs::CAPACITY as u32)
+            .map(Input::Custom)
+            .collect();
+        let action_inputs = ActionInputs::from_slice(&inputs);
+        assert_eq!(*action_inputs, inputs[..ActionInputs::CAPACITY]);
+        assert_eq!(ActionInputs::from_slice(&[]), ActionInputs::default());
+    }
+
     #[derive(Debug, InputAction)]
     #[input_action(output = bool)]
     struct DummyAction;
@@ -3,7 +3,10 @@ use std::fmt::Debug;
 use bevy::prelude::*;
 use bitflags::bitflags;
 
-use super::{context_instance::ActionState, input_action::InputAction};
+use super::{
+    context_instance::{ActionInputs, ActionState},
+    input_action::InputAction,
+};
 
 bitflags! {
     /// Bitset with events triggered by updating [`ActionState`] for an action.
@@ -76,6 +79,16 @@ pub struct Started<A: InputAction> {
 
     /// Current action state.
     pub state: ActionState,
+
+    /// Inputs that drove the action.
+    ///
+    /// See [`ActionData::inputs`](super::context_instance::ActionData::inputs).
+    pub inputs: ActionInputs,
+
+    /// Gamepad that provided the input.
+    ///
+    /// See [`ActionData::gamepad`](super::context_instance::ActionData::gamepad).
+    pub gamepad: Option<Entity>,
 }
 
 impl<A: InputAction> Clone for Started<A> {
@@ -122,6 +135,16 @@ pub struct Fired<A: InputAction> {
     /// Current action state.
     pub state: ActionState,
 
+    /// Inputs that drove the action.
+    ///
+    /// See [`ActionData::inputs`](super::context_instance::ActionData::inputs).
+    pub inputs: ActionInputs,
+
+    /// Gamepad that provided the input.
+    ///
+    /// See [`ActionData::gamepad`](super::context_instance::ActionData::gamepad).
+    pub gamepad: Option<Entity>,
+
     /// Time that this action has been in [`ActionState::Fired`] state.
     pub fired_secs: f32,
 
@@ -173,6 +196,16 @@ pub struct Completed<A: InputAction> {
     /// Current action state.
     pub state: ActionState,
 
+    /// Inputs that drove the action.
+    ///
+    /// See [`ActionData::inputs`](super::context_instance::ActionData::inputs).
+    pub inputs: ActionInputs,
+
+    /// Gamepad that provided the input.
+    ///
+    /// See [`ActionData::gamepad`](super::context_instance::ActionData::gamepad).
+    pub gamepad: Option<Entity>,
+
     /// Time that this action has been in [`ActionState::Fired`] state.
     pub fired_secs: f32,
 
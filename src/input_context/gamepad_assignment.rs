@@ -0,0 +1,235 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+};
+
+use super::ContextInstances;
+use crate::input::{gamepad_set::GamepadSet, GamepadDevice};
+
+/// Configures automatic assignment of gamepads to entities with [`GamepadSlot`].
+///
+/// Assignment is opt-in: gamepads are assigned only to entities with [`GamepadSlot`].
+/// The assigned gamepad is applied to all exclusive
+/// [`ContextInstance`](super::context_instance::ContextInstance)s of the entity
+/// without rebuilding them. Shared contexts are not affected.
+///
+/// Free slots are filled in the order their [`GamepadSlot`] components were inserted.
+/// Triggers [`GamepadJoined`], [`GamepadLeft`] and [`GamepadReassigned`] on the slot entity.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(GamepadAssignment {
+///     join: GamepadJoin::OnPress(GamepadButton::Start),
+///     disconnect: GamepadDisconnect::Fallback,
+/// });
+///
+/// app.world_mut().spawn((Player, GamepadSlot::default()));
+/// app.world_mut().spawn((Player, GamepadSlot::default()));
+/// # #[derive(Component)]
+/// # struct Player;
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GamepadAssignment {
+    /// When a gamepad takes a free slot.
+    pub join: GamepadJoin,
+    /// What happens to a slot when its gamepad disconnects.
+    pub disconnect: GamepadDisconnect,
+}
+
+impl GamepadAssignment {
+    pub(crate) fn update(
+        mut commands: Commands,
+        assignment: Res<Self>,
+        mut connection_events: EventReader<GamepadConnectionEvent>,
+        mut instances: ResMut<ContextInstances>,
+        mut slots: Query<(Entity, &mut GamepadSlot)>,
+        gamepads: Query<(Entity, &Gamepad)>,
+    ) {
+        for (entity, slot) in &mut slots {
+            if slot.is_added() {
+                instances.set_gamepad(entity, slot.device());
+            }
+        }
+
+        let mut vacated = Vec::new();
+        for event in connection_events.read() {
+            let Some((entity, mut slot)) = slots
+                .iter_mut()
+                .find(|(_, slot)| slot.gamepad == Some(event.gamepad))
+            else {
+                continue;
+            };
+
+            match event.connection {
+                GamepadConnection::Connected { .. } => {
+                    debug!("reconnecting `{}` to `{entity}`", event.gamepad);
+                    commands.trigger_targets(
+                        GamepadJoined {
+                            gamepad: event.gamepad,
+                        },
+                        entity,
+                    );
+                }
+                GamepadConnection::Disconnected => {
+                    debug!("disconnecting `{}` from `{entity}`", event.gamepad);
+                    commands.trigger_targets(
+                        GamepadLeft {
+                            gamepad: event.gamepad,
+                        },
+                        entity,
+                    );
+                    if assignment.disconnect == GamepadDisconnect::Fallback {
+                        slot.gamepad = None;
+                        instances.set_gamepad(entity, slot.device());
+                        vacated.push((entity, event.gamepad));
+                    }
+                }
+            }
+        }
+
+        let mut free_gamepads: Vec<_> = gamepads
+            .iter()
+            .filter(|&(gamepad_entity, _)| {
+                !slots
+                    .iter()
+                    .any(|(_, slot)| slot.gamepad == Some(gamepad_entity))
+            })
+            .collect();
+        free_gamepads.sort_by_key(|&(gamepad_entity, _)| gamepad_entity);
+
+        for (entity, previous) in vacated {
+            if free_gamepads.is_empty() {
+                break;
+            }
+            let (gamepad_entity, _) = free_gamepads.remove(0);
+            let (_, mut slot) = slots.get_mut(entity).unwrap();
+            debug!("reassigning `{entity}` from `{previous}` to `{gamepad_entity}`");
+            slot.gamepad = Some(gamepad_entity);
+            instances.set_gamepad(entity, slot.device());
+            commands.trigger_targets(
+                GamepadReassigned {
+                    from: previous,
+                    to: gamepad_entity,
+                },
+                entity,
+            );
+        }
+
+        free_gamepads.retain(|(_, gamepad)| match assignment.join {
+            GamepadJoin::OnConnect => true,
+            GamepadJoin::OnPress(button) => gamepad.just_pressed(button),
+        });
+        if free_gamepads.is_empty() {
+            return;
+        }
+
+        let mut free_slots: Vec<_> = slots
+            .iter_mut()
+            .filter(|(_, slot)| slot.gamepad.is_none())
+            .collect();
+        free_slots.sort_by_key(|(_, slot)| slot.order);
+
+        for ((entity, mut slot), (gamepad_entity, _)) in free_slots.into_iter().zip(free_gamepads) {
+            debug!("assigning `{gamepad_entity}` to `{entity}`");
+            slot.gamepad = Some(gamepad_entity);
+            instances.set_gamepad(entity, slot.device());
+            commands.trigger_targets(
+                GamepadJoined {
+                    gamepad: gamepad_entity,
+                },
+                entity,
+            );
+        }
+    }
+}
+
+/// Defines when a gamepad takes a free [`GamepadSlot`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GamepadJoin {
+    /// Any connected gamepad without a slot joins immediately.
+    #[default]
+    OnConnect,
+    /// A connected gamepad without a slot joins when the button is pressed.
+    OnPress(GamepadButton),
+}
+
+/// Defines what happens to a [`GamepadSlot`] when its gamepad disconnects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GamepadDisconnect {
+    /// Keep the slot reserved until the same gamepad reconnects.
+    ///
+    /// Contexts read nothing from gamepads in the meantime.
+    #[default]
+    Pause,
+    /// Free the slot and assign another connected gamepad without a slot.
+    ///
+    /// The next gamepad is picked regardless of [`GamepadJoin`].
+    /// If there are no such gamepads, the slot waits for a new one to join.
+    Fallback,
+}
+
+/// Marks an entity to receive a gamepad automatically according to [`GamepadAssignment`].
+///
+/// Until a gamepad is assigned, contexts of the entity read nothing from gamepads.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[component(on_add = Self::on_add)]
+pub struct GamepadSlot {
+    gamepad: Option<Entity>,
+    /// Insertion sequence number to fill free slots in the spawn order.
+    ///
+    /// Entity IDs can't be used for it because despawned IDs are reused.
+    order: u64,
+}
+
+impl GamepadSlot {
+    /// Returns the assigned gamepad.
+    ///
+    /// The gamepad may be disconnected if [`GamepadDisconnect::Pause`] is used.
+    pub fn gamepad(&self) -> Option<Entity> {
+        self.gamepad
+    }
+
+    /// Returns the device that contexts of the entity should read.
+    pub(super) fn device(&self) -> GamepadDevice {
+        match self.gamepad {
+            Some(entity) => GamepadDevice::Single(entity),
+            None => GamepadDevice::Set(GamepadSet::default()),
+        }
+    }
+
+    fn on_add(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+        static NEXT_ORDER: AtomicU64 = AtomicU64::new(0);
+
+        let mut slot = world.get_mut::<Self>(entity).unwrap();
+        slot.order = NEXT_ORDER.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Triggers on a [`GamepadSlot`] entity when a gamepad is assigned to it or reconnects to it.
+#[derive(Debug, Event, Clone, Copy)]
+pub struct GamepadJoined {
+    /// Assigned gamepad.
+    pub gamepad: Entity,
+}
+
+/// Triggers on a [`GamepadSlot`] entity when its gamepad disconnects.
+#[derive(Debug, Event, Clone, Copy)]
+pub struct GamepadLeft {
+    /// Disconnected gamepad.
+    pub gamepad: Entity,
+}
+
+/// Triggers on a [`GamepadSlot`] entity when its disconnected gamepad is replaced
+/// according to [`GamepadDisconnect::Fallback`].
+#[derive(Debug, Event, Clone, Copy)]
+pub struct GamepadReassigned {
+    /// Disconnected gamepad.
+    pub from: Entity,
+    /// New gamepad.
+    pub to: Entity,
+}
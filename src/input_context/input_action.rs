@@ -47,7 +47,7 @@ use crate::action_value::{ActionValue, ActionValueDim};
 /// struct Move;
 /// ```
 ///
-/// Optionally you can pass `consume_input` and/or `accumulation`:
+/// Optionally you can pass `consume_input`, `accumulation`, `ignore_ui_focus` and/or `active_in_text_input`:
 ///
 /// ```
 /// # use bevy::prelude::*;
@@ -55,6 +55,14 @@ use crate::action_value::{ActionValue, ActionValueDim};
 /// #[derive(Debug, InputAction)]
 /// #[input_action(output = Vec2, accumulation = Cumulative, consume_input = false)]
 /// struct Move;
+///
+/// #[derive(Debug, InputAction)]
+/// #[input_action(output = bool, ignore_ui_focus = true)]
+/// struct Pause;
+///
+/// #[derive(Debug, InputAction)]
+/// #[input_action(output = bool, active_in_text_input = true)]
+/// struct SendMessage;
 /// ```
 pub trait InputAction: Debug + Send + Sync + 'static {
     /// What type of value this action will output.
@@ -80,6 +88,19 @@ pub trait InputAction: Debug + Send + Sync + 'static {
 
     /// Associated accumulation behavior.
     const ACCUMULATION: Accumulation = Accumulation::Cumulative;
+
+    /// Specifies whether this action should read inputs owned by UI.
+    ///
+    /// Useful for actions that should always work, like opening a pause menu with Escape.
+    /// See [`InputFocus`](crate::input::input_focus::InputFocus) for details.
+    const IGNORE_UI_FOCUS: bool = false;
+
+    /// Specifies whether this action should read keyboard while
+    /// [`TextInputMode`](crate::input::text_input::TextInputMode) is active.
+    ///
+    /// Useful for actions that control text entry itself, like sending a message with Enter
+    /// or closing a chat box with Escape.
+    const ACTIVE_IN_TEXT_INPUT: bool = false;
 }
 
 /// Marks a type which can be used as [`InputAction::Output`].
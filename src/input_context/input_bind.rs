@@ -4,7 +4,10 @@ use super::{
     input_condition::{InputCondition, InputConditions},
     input_modifier::{InputModifier, InputModifiers},
 };
-use crate::input::Input;
+use crate::{
+    action_value::ActionValue,
+    input::{input_reader::InputReader, Input, ModKeysMatching},
+};
 
 /// Associated input for [`ActionBind`](super::context_instance::ActionBind).
 #[derive(Debug)]
@@ -12,6 +15,12 @@ pub struct InputBind {
     pub input: Input,
     pub modifiers: Vec<Box<dyn InputModifier>>,
     pub conditions: Vec<Box<dyn InputCondition>>,
+    pub mod_keys_matching: ModKeysMatching,
+
+    /// Minimum magnitude of the input value to consider it actuated.
+    ///
+    /// Values below it are read as zero. By default it's 0.0, so any non-zero value is actuated.
+    pub actuation: f32,
 
     /// Newly created mappings are ignored by default until until a zero
     /// value is read for them.
@@ -19,6 +28,9 @@ pub struct InputBind {
     /// This prevents newly created contexts from reacting to currently
     /// held inputs until they are released.
     pub(super) ignored: bool,
+
+    /// Blocked by [`ClashStrategy`](super::clash_strategy::ClashStrategy) for the current frame.
+    pub(super) clashed: bool,
 }
 
 impl InputBind {
@@ -28,7 +40,26 @@ impl InputBind {
             input: input.into(),
             modifiers: Default::default(),
             conditions: Default::default(),
+            mod_keys_matching: Default::default(),
+            actuation: 0.0,
             ignored: true,
+            clashed: false,
+        }
+    }
+
+    /// Returns `true` if [`ModKeysMatching::Exact`] is used and other modifiers are held.
+    pub(super) fn has_extra_mod_keys(&self, reader: &InputReader) -> bool {
+        self.mod_keys_matching == ModKeysMatching::Exact
+            && reader.extra_mod_keys_pressed(self.input)
+    }
+
+    /// Reads the input value, returning zero if it's below [`Self::actuation`].
+    pub(super) fn read(&self, reader: &mut InputReader) -> ActionValue {
+        let value = reader.value(self.input);
+        if value.is_actuated(self.actuation) {
+            value
+        } else {
+            ActionValue::zero(value.dim())
         }
     }
 }
@@ -48,6 +79,41 @@ pub trait InputBindModCond {
     /// Adds conditions.
     #[must_use]
     fn with_conditions(self, condition: impl InputConditions) -> InputBind;
+
+    /// Sets how held keyboard modifiers are matched.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// # let mut ctx = ContextInstance::default();
+    /// ctx.bind::<Duplicate>().to(KeyCode::KeyD
+    ///     .with_mod_keys(ModKeys::SHIFT_LEFT)
+    ///     .with_mod_keys_matching(ModKeysMatching::Exact));
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Duplicate;
+    /// ```
+    #[must_use]
+    fn with_mod_keys_matching(self, matching: ModKeysMatching) -> InputBind;
+
+    /// Sets the minimum magnitude required to consider the input actuated.
+    ///
+    /// Useful as a press threshold for analog inputs like
+    /// [`Input::GamepadAnalog`]. Unlike conditions, it's also respected when
+    /// resolving clashes and deciding whether held inputs are released.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_enhanced_input::prelude::*;
+    /// # let mut ctx = ContextInstance::default();
+    /// ctx.bind::<Fire>()
+    ///     .to(Input::gamepad_analog(GamepadButton::RightTrigger2).with_actuation(0.5));
+    /// # #[derive(Debug, InputAction)]
+    /// # #[input_action(output = bool)]
+    /// # struct Fire;
+    /// ```
+    #[must_use]
+    fn with_actuation(self, actuation: f32) -> InputBind;
 }
 
 impl<T: Into<InputBind>> InputBindModCond for T {
@@ -62,6 +128,18 @@ impl<T: Into<InputBind>> InputBindModCond for T {
         binding.conditions.extend(condition.iter_conditions());
         binding
     }
+
+    fn with_mod_keys_matching(self, matching: ModKeysMatching) -> InputBind {
+        let mut binding = self.into();
+        binding.mod_keys_matching = matching;
+        binding
+    }
+
+    fn with_actuation(self, actuation: f32) -> InputBind {
+        let mut binding = self.into();
+        binding.actuation = actuation;
+        binding
+    }
 }
 
 /// Represents collection of bindings that could be passed into
@@ -5,6 +5,7 @@ pub mod delta_scale;
 pub mod exponential_curve;
 pub mod negate;
 pub mod scale;
+pub mod steps;
 pub mod swizzle_axis;
 
 use std::{fmt::Debug, iter};
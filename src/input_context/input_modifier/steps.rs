@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use super::InputModifier;
+use crate::{action_value::ActionValue, input_context::context_instance::ActionsData};
+
+/// Splits accumulated input into discrete steps and outputs at most one step per frame.
+///
+/// Input is accumulated across frames and each time the accumulated value reaches [`Self::size`],
+/// the modifier outputs `1.0` (or `-1.0` for negative values) and subtracts one step.
+/// If multiple steps are accumulated, the remaining steps are output on the next frames.
+/// Applied independently to each axis.
+///
+/// Useful for [`Input::MouseWheelDirection`](crate::input::Input::MouseWheelDirection)
+/// to trigger once per wheel notch regardless of the scrolling speed or
+/// to avoid triggering every frame for smooth scrolling devices like touchpads.
+///
+/// Since steps from a single burst are output on consecutive frames without a zero in between,
+/// count them using [`Fired`](crate::input_context::events::Fired) without conditions or with
+/// [`Press`](crate::input_context::input_condition::press::Press).
+/// [`Started`](crate::input_context::events::Started) and conditions like
+/// [`JustPress`](crate::input_context::input_condition::just_press::JustPress)
+/// trigger only once per burst.
+///
+/// [`ActionValue::Bool`] will be passed as is.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_enhanced_input::prelude::*;
+/// # let mut ctx = ContextInstance::default();
+/// ctx.bind::<NextWeapon>()
+///     .to(Input::mouse_wheel_direction(MouseDirection::Up).with_modifiers(Steps::default()));
+/// # #[derive(Debug, InputAction)]
+/// # #[input_action(output = bool)]
+/// # struct NextWeapon;
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Steps {
+    size: f32,
+    accumulated: Vec3,
+}
+
+impl Steps {
+    /// Creates a new instance with the given step size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` isn't positive.
+    #[must_use]
+    pub fn new(size: f32) -> Self {
+        assert!(size > 0.0, "step size should be positive, but got {size}");
+        Self {
+            size,
+            accumulated: Vec3::ZERO,
+        }
+    }
+
+    /// Returns accumulated value required for a single step.
+    ///
+    /// By default set to 1.0, which corresponds to a single wheel notch
+    /// for [`MouseScrollUnit::Line`](bevy::input::mouse::MouseScrollUnit::Line).
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+}
+
+impl Default for Steps {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl InputModifier for Steps {
+    fn apply(
+        &mut self,
+        _actions: &ActionsData,
+        _time: &Time<Virtual>,
+        value: ActionValue,
+    ) -> ActionValue {
+        if let ActionValue::Bool(value) = value {
+            return value.into();
+        }
+
+        self.accumulated += value.as_axis3d();
+        let steps = self.accumulated / self.size;
+        let step = Vec3::select(steps.abs().cmpge(Vec3::ONE), steps.signum(), Vec3::ZERO);
+        self.accumulated -= step * self.size;
+
+        ActionValue::Axis3D(step).convert(value.dim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        let mut modifier = Steps::default();
+        let actions = ActionsData::default();
+        let time = Time::default();
+
+        assert_eq!(modifier.apply(&actions, &time, 0.4.into()), 0.0.into());
+        assert_eq!(modifier.apply(&actions, &time, 0.7.into()), 1.0.into());
+        assert_eq!(
+            modifier.apply(&actions, &time, 2.0.into()),
+            1.0.into(),
+            "only a single step should be output per frame"
+        );
+        assert_eq!(modifier.apply(&actions, &time, 0.0.into()), 1.0.into());
+        assert_eq!(modifier.apply(&actions, &time, 0.0.into()), 0.0.into());
+
+        let mut modifier = Steps::new(2.0);
+        assert_eq!(
+            modifier.apply(&actions, &time, Vec2::new(-2.0, 1.0).into()),
+            (-1.0, 0.0).into()
+        );
+        assert_eq!(modifier.apply(&actions, &time, true.into()), true.into());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_size() {
+        let _ = Steps::new(0.0);
+    }
+}
@@ -38,11 +38,21 @@ For more details, see the documentation on relevant types. You can also find exa
 
 # Input and UI
 
-Currently, Bevy does not have a focus management API. However, to prevent actions from being triggered
-while interacting with the UI, we implement temporary workarounds enabled by specific cargo features:
+To prevent actions from being triggered while interacting with the UI, we track which UI layer owns
+the keyboard and the pointer in the [`InputFocus`] resource. Inputs owned by UI are discarded.
+The focus is updated based on specific cargo features:
 
-* If the `ui_priority` feature is enabled, we check if any [`Interaction`] component is not [`Interaction::None`] and discard all mouse inputs.
-* If the `egui_priority` feature is enabled, we check if any egui context requires keyboard or mouse input and discard those inputs accordingly.
+* If the `ui_priority` feature is enabled, hovered or pressed nodes with `FocusPolicy::Block` own the pointer.
+* If the `egui_priority` feature is enabled, egui owns keyboard or pointer when any of its contexts requires the input.
+* If the `picking` feature is enabled, hovered nodes from `bevy_picking` own the pointer instead of relying on `Interaction`.
+  This feature also adds [`Input::EntityPress`] to bind pointer presses on entities that match a filter.
+
+Contexts and actions that should always receive input can opt out with
+[`InputContext::IGNORE_UI_FOCUS`] and [`InputAction::IGNORE_UI_FOCUS`].
+
+While the keyboard is used for text entry, like typing into a chat box, keyboard inputs are blocked
+via [`TextInputMode`]. It's activated manually or automatically when IME is enabled on the focused window.
+Actions that control text entry can opt out with [`InputAction::ACTIVE_IN_TEXT_INPUT`].
 
 # Troubleshooting
 
@@ -67,12 +77,35 @@ pub mod input;
 pub mod input_context;
 
 pub mod prelude {
+    #[cfg(feature = "picking")]
+    pub use super::input::picking::{PickingAppExt, PressedEntities};
     pub use super::{
         action_value::{ActionValue, ActionValueDim},
-        input::{GamepadDevice, Input, InputModKeys, ModKeys},
+        input::{
+            gamepad_aggregation::GamepadAggregation,
+            gamepad_set::GamepadSet,
+            input_capture::{CaptureDevices, CaptureSettings, InputCapture, InputCaptured},
+            input_combo::{ComboButton, InputCombo},
+            input_focus::{FocusOwner, InputFocus},
+            input_prompt::{GamepadFamily, InputPrompt},
+            input_recorder::{InputRecorder, InputRecording, RecordedFrame, RecordedValue},
+            input_source::{InputSource, InputSourceAppExt},
+            last_input_device::{InputDevice, InputDeviceChanged, LastInputDevice},
+            text_input::TextInputMode,
+            virtual_input::VirtualInput,
+            GamepadDevice, GamepadModButtons, Input, InputModButtons, InputModKeys, ModKeys,
+            ModKeysMatching, MouseDirection, ParseInputError,
+        },
         input_context::{
-            context_instance::{ActionBind, ActionData, ActionState, ContextInstance},
+            clash_strategy::ClashStrategy,
+            context_instance::{
+                ActionBind, ActionData, ActionInputs, ActionState, ContextInstance,
+            },
             events::*,
+            gamepad_assignment::{
+                GamepadAssignment, GamepadDisconnect, GamepadJoin, GamepadJoined, GamepadLeft,
+                GamepadReassigned, GamepadSlot,
+            },
             input_action::{Accumulation, InputAction},
             input_bind::{InputBind, InputBindModCond, InputBindings},
             input_condition::{
@@ -82,7 +115,8 @@ pub mod prelude {
             },
             input_modifier::{
                 accumulate_by::*, dead_zone::*, delta_lerp::*, delta_scale::*,
-                exponential_curve::*, negate::*, scale::*, swizzle_axis::*, InputModifier,
+                exponential_curve::*, negate::*, scale::*, steps::*, swizzle_axis::*,
+                InputModifier,
             },
             preset::{Bidirectional, Cardinal, GamepadStick},
             ContextAppExt, ContextInstances, ContextMode, InputContext, RebuildInputContexts,
@@ -92,9 +126,9 @@ pub mod prelude {
     pub use bevy_enhanced_input_macros::InputAction;
 }
 
-use bevy::{input::InputSystem, prelude::*};
+use bevy::{input::InputSystem, prelude::*, window::WindowFocused};
 
-use input::input_reader::InputReader;
+use input::{input_reader::InputReader, input_source::InputSources};
 use prelude::*;
 
 /// Initializes contexts and feeds inputs to them.
@@ -103,20 +137,100 @@ pub struct EnhancedInputPlugin;
 impl Plugin for EnhancedInputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ContextInstances>()
+            .init_resource::<VirtualInput>()
+            .init_resource::<InputSources>()
+            .init_resource::<InputRecorder>()
+            .init_resource::<InputCapture>()
+            .init_resource::<LastInputDevice>()
+            .init_resource::<ClashStrategy>()
+            .init_resource::<GamepadAggregation>()
+            .init_resource::<GamepadAssignment>()
+            .init_resource::<InputFocus>()
+            .init_resource::<TextInputMode>()
+            .add_event::<WindowFocused>()
             .configure_sets(PreUpdate, EnhancedInputSystem.after(InputSystem))
-            .add_systems(PreUpdate, Self::update.in_set(EnhancedInputSystem));
+            .add_systems(
+                PreUpdate,
+                (
+                    InputSources::update,
+                    GamepadAssignment::update,
+                    InputFocus::update,
+                    TextInputMode::update,
+                    #[cfg(feature = "picking")]
+                    PressedEntities::update,
+                    Self::reset_on_focus_loss,
+                    Self::ignore_keys_after_text_input,
+                    Self::update,
+                )
+                    .chain()
+                    .in_set(EnhancedInputSystem),
+            );
+
+        #[cfg(feature = "picking")]
+        app.init_resource::<PressedEntities>().configure_sets(
+            PreUpdate,
+            EnhancedInputSystem.after(bevy::picking::PickSet::Focus),
+        );
     }
 }
 
 impl EnhancedInputPlugin {
+    fn reset_on_focus_loss(
+        mut commands: Commands,
+        mut focus_events: EventReader<WindowFocused>,
+        time: Res<Time<Virtual>>,
+        mut instances: ResMut<ContextInstances>,
+    ) {
+        // Focus could move to another window of the app, so check only the latest event.
+        if focus_events
+            .read()
+            .last()
+            .is_some_and(|event| !event.focused)
+        {
+            debug!("resetting contexts on focus loss");
+            instances.reset_on_focus_loss(&mut commands, &time);
+        }
+    }
+
+    fn ignore_keys_after_text_input(
+        mut was_active: Local<bool>,
+        text_input: Res<TextInputMode>,
+        mut instances: ResMut<ContextInstances>,
+    ) {
+        let active = text_input.is_active();
+        if *was_active && !active {
+            debug!("ignoring held keys after text input");
+            instances.ignore_held_keys();
+        }
+        *was_active = active;
+    }
+
     fn update(
         mut commands: Commands,
         mut reader: InputReader,
         time: Res<Time<Virtual>>, // We explicitly use `Virtual` to have access to `relative_speed`.
         mut instances: ResMut<ContextInstances>,
+        mut capture: ResMut<InputCapture>,
+        mut last_device: ResMut<LastInputDevice>,
+        strategy: Res<ClashStrategy>,
     ) {
         reader.update_state();
-        instances.update(&mut commands, &mut reader, &time);
+        reader.update_actuated_devices(last_device.actuation());
+        last_device.update(&mut commands, reader.actuated_devices());
+        let time = reader.next_recorded_frame(&time);
+
+        if let Some(settings) = capture.settings() {
+            instances.reset(&mut commands, &time);
+            if let Some(input) = reader.capture(settings) {
+                let input = (!settings.is_cancel(input)).then_some(input);
+                debug!("finishing input capture with `{input:?}`");
+                capture.stop();
+                commands.trigger(InputCaptured { input });
+            }
+            return;
+        }
+
+        instances.update(&mut commands, &mut reader, &time, *strategy);
     }
 }
 
@@ -0,0 +1,153 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn keyboard() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Triggered>()
+        .add_observer(store_started)
+        .add_observer(store_completed)
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.inputs(), [Jump::KEY.into()]);
+    assert_eq!(action.gamepad(), None);
+
+    let triggered = app.world().resource::<Triggered>();
+    assert_eq!(triggered.started, Some((vec![Jump::KEY.into()], None)));
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::None);
+    assert_eq!(
+        action.inputs(),
+        [Jump::KEY.into()],
+        "inputs should be preserved after release"
+    );
+
+    let triggered = app.world().resource::<Triggered>();
+    assert_eq!(triggered.completed, Some((vec![Jump::KEY.into()], None)));
+}
+
+#[test]
+fn gamepad() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Triggered>()
+        .add_observer(store_started)
+        .add_observer(store_completed)
+        .add_input_context::<Player>();
+
+    app.world_mut().spawn(Gamepad::default());
+    let gamepad_entity = app.world_mut().spawn(Gamepad::default()).id();
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.inputs(), [Jump::BUTTON.into()]);
+    assert_eq!(action.gamepad(), Some(gamepad_entity));
+
+    let triggered = app.world().resource::<Triggered>();
+    assert_eq!(
+        triggered.started,
+        Some((vec![Jump::BUTTON.into()], Some(gamepad_entity)))
+    );
+}
+
+#[test]
+fn accumulated() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Move::UP_KEY);
+    keys.press(Move::RIGHT_KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Move>().unwrap();
+    assert_eq!(
+        action.inputs(),
+        [Move::UP_KEY.into(), Move::RIGHT_KEY.into()]
+    );
+}
+
+fn store_started(trigger: Trigger<Started<Jump>>, mut triggered: ResMut<Triggered>) {
+    triggered.started = Some((trigger.inputs.to_vec(), trigger.gamepad));
+}
+
+fn store_completed(trigger: Trigger<Completed<Jump>>, mut triggered: ResMut<Triggered>) {
+    triggered.completed = Some((trigger.inputs.to_vec(), trigger.gamepad));
+}
+
+/// Inputs and gamepads from the last triggered events.
+#[derive(Resource, Default)]
+struct Triggered {
+    started: Option<(Vec<Input>, Option<Entity>)>,
+    completed: Option<(Vec<Input>, Option<Entity>)>,
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to((Jump::KEY, Jump::BUTTON));
+        ctx.bind::<Move>().to(Cardinal::wasd_keys());
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+    const BUTTON: GamepadButton = GamepadButton::South;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct Move;
+
+impl Move {
+    const UP_KEY: KeyCode = KeyCode::KeyW;
+    const RIGHT_KEY: KeyCode = KeyCode::KeyD;
+}
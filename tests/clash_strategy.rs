@@ -0,0 +1,172 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn all_fire() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn((Player, Editor)).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ControlLeft);
+    keys.press(KeyCode::KeyS);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Crouch>().unwrap().state(), ActionState::Fired);
+
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Save>().unwrap().state(),
+        ActionState::None,
+        "`KeyS` should be consumed by the context with a higher priority"
+    );
+}
+
+#[test]
+fn prefer_largest() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(ClashStrategy::PreferLargest)
+        .add_input_context::<Player>()
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn((Player, Editor)).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ControlLeft);
+    keys.press(KeyCode::KeyS);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Crouch>().unwrap().state(),
+        ActionState::None,
+        "`KeyS` should be shadowed by `Ctrl+KeyS` from another context"
+    );
+
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(ctx.action::<Save>().unwrap().state(), ActionState::Fired);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(KeyCode::ControlLeft);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Crouch>().unwrap().state(), ActionState::Fired);
+
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(ctx.action::<Save>().unwrap().state(), ActionState::None);
+}
+
+#[test]
+fn first_wins() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(ClashStrategy::FirstWins)
+        .add_input_context::<Player>()
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn((Player, Editor)).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ControlLeft);
+    keys.press(KeyCode::KeyS);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Crouch>().unwrap().state(),
+        ActionState::Fired,
+        "context with a higher priority should be evaluated first"
+    );
+
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(ctx.action::<Save>().unwrap().state(), ActionState::None);
+}
+
+#[test]
+fn gamepad() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(ClashStrategy::PreferLargest)
+        .add_input_context::<Player>();
+
+    let gamepad_entity = app.world_mut().spawn(Gamepad::default()).id();
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.digital_mut().press(GamepadButton::LeftTrigger);
+    gamepad.digital_mut().press(GamepadButton::South);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+    assert_eq!(ctx.action::<Roll>().unwrap().state(), ActionState::Fired);
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Crouch>().to(KeyCode::KeyS);
+        ctx.bind::<Jump>().to(GamepadButton::South);
+        ctx.bind::<Roll>()
+            .to(GamepadButton::South.with_mod_buttons(GamepadModButtons::LEFT_TRIGGER));
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Editor;
+
+impl InputContext for Editor {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Save>()
+            .to(KeyCode::KeyS.with_mod_keys(ModKeys::CONTROL));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Crouch;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Roll;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Save;
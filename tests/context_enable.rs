@@ -0,0 +1,152 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn toggle() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .init_resource::<CompletedCount>()
+        .add_observer(record_completed);
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_enabled::<Player>(entity, false);
+
+    app.update();
+
+    assert_eq!(**app.world().resource::<CompletedCount>(), 1);
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert!(!ctx.is_enabled());
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+
+    app.update();
+
+    assert_eq!(
+        **app.world().resource::<CompletedCount>(),
+        1,
+        "events should be triggered only once"
+    );
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_enabled::<Player>(entity, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert!(ctx.is_enabled());
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "held inputs should be ignored after enabling"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn passthrough() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Swimming>();
+
+    let entity = app.world_mut().spawn((Player, Swimming)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_enabled::<Swimming>(entity, false);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Swimming>(entity).unwrap();
+    assert_eq!(ctx.action::<Dive>().unwrap().state(), ActionState::None);
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::Fired,
+        "disabled context shouldn't consume inputs"
+    );
+}
+
+fn record_completed(_trigger: Trigger<Completed<Jump>>, mut count: ResMut<CompletedCount>) {
+    **count += 1;
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct CompletedCount(usize);
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Swimming;
+
+impl InputContext for Swimming {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Dive>().to(Jump::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Dive;
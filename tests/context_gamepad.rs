@@ -75,6 +75,93 @@ fn by_id() {
     assert_eq!(action.state(), ActionState::None);
 }
 
+#[test]
+fn set() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<MultipleGamepads>();
+
+    let gamepad_entity1 = app.world_mut().spawn(Gamepad::default()).id();
+    let gamepad_entity2 = app.world_mut().spawn(Gamepad::default()).id();
+    let gamepad_entity3 = app.world_mut().spawn(Gamepad::default()).id();
+
+    let set = GamepadSet::default()
+        .with(gamepad_entity1)
+        .with(gamepad_entity2);
+    let context_entity = app.world_mut().spawn(MultipleGamepads(set.into())).id();
+
+    app.update();
+
+    for gamepad_entity in [gamepad_entity1, gamepad_entity2] {
+        let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+        gamepad.analog_mut().set(MoveX::AXIS, 0.25);
+    }
+
+    let mut gamepad3 = app.world_mut().get_mut::<Gamepad>(gamepad_entity3).unwrap();
+    gamepad3.digital_mut().press(DummyAction::BUTTON);
+    gamepad3.analog_mut().set(MoveX::AXIS, 1.0);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<MultipleGamepads>(context_entity).unwrap();
+    assert_eq!(
+        ctx.action::<DummyAction>().unwrap().state(),
+        ActionState::None,
+        "gamepads outside the set should be ignored"
+    );
+    assert_eq!(
+        ctx.action::<MoveX>().unwrap().value(),
+        ActionValue::Axis1D(0.5),
+        "axis values from all gamepads in the set should be summed"
+    );
+
+    let mut gamepad2 = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad2.digital_mut().press(DummyAction::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<MultipleGamepads>(context_entity).unwrap();
+    let action = ctx.action::<DummyAction>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+}
+
+#[test]
+fn except() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<MultipleGamepads>();
+
+    let gamepad_entity1 = app.world_mut().spawn(Gamepad::default()).id();
+    let gamepad_entity2 = app.world_mut().spawn(Gamepad::default()).id();
+
+    let device = GamepadDevice::Except(GamepadSet::default().with(gamepad_entity1));
+    let context_entity = app.world_mut().spawn(MultipleGamepads(device)).id();
+
+    app.update();
+
+    let mut gamepad1 = app.world_mut().get_mut::<Gamepad>(gamepad_entity1).unwrap();
+    gamepad1.digital_mut().press(DummyAction::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<MultipleGamepads>(context_entity).unwrap();
+    let action = ctx.action::<DummyAction>().unwrap();
+    assert_eq!(action.state(), ActionState::None);
+
+    let mut gamepad2 = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad2.digital_mut().press(DummyAction::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<MultipleGamepads>(context_entity).unwrap();
+    let action = ctx.action::<DummyAction>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+}
+
 #[derive(Debug, Component)]
 struct AnyGamepad;
 
@@ -108,3 +195,27 @@ struct DummyAction;
 impl DummyAction {
     const BUTTON: GamepadButton = GamepadButton::South;
 }
+
+#[derive(Debug, Component, Deref)]
+struct MultipleGamepads(GamepadDevice);
+
+impl InputContext for MultipleGamepads {
+    fn context_instance(world: &World, entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+
+        let gamepad = **world.get::<Self>(entity).unwrap();
+        ctx.set_gamepad(gamepad);
+        ctx.bind::<DummyAction>().to(DummyAction::BUTTON);
+        ctx.bind::<MoveX>().to(MoveX::AXIS);
+
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+struct MoveX;
+
+impl MoveX {
+    const AXIS: GamepadAxis = GamepadAxis::LeftStickX;
+}
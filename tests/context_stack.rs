@@ -0,0 +1,493 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn push_blocking() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity = app.world_mut().spawn((Player, Inventory)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity, true);
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Jump::KEY);
+    keys.press(DropItem::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    assert_eq!(instances.layers_count(entity), 1);
+
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<DropItem>().unwrap().state(),
+        ActionState::Fired
+    );
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "lower contexts should read inputs as consumed"
+    );
+}
+
+#[test]
+fn push_non_blocking() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity = app.world_mut().spawn((Player, Inventory)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity, false);
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Jump::KEY);
+    keys.press(DropItem::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<DropItem>().unwrap().state(),
+        ActionState::Fired,
+        "pushed context should be evaluated first despite lower priority"
+    );
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+    assert_eq!(
+        ctx.action::<Attack>().unwrap().state(),
+        ActionState::None,
+        "input should be consumed by the pushed context"
+    );
+}
+
+#[test]
+fn push_held() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity = app.world_mut().spawn((Player, Inventory)).id();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(DropItem::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<DropItem>().unwrap().state(),
+        ActionState::None,
+        "inputs held before the push shouldn't fire"
+    );
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Attack>().unwrap().state(), ActionState::None);
+}
+
+#[test]
+fn pop() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity = app.world_mut().spawn((Player, Inventory)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let mut instances = app.world_mut().resource_mut::<ContextInstances>();
+    assert!(instances.pop_layer(entity));
+    assert!(!instances.pop_layer(entity), "stack should be empty");
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    assert_eq!(instances.layers_count(entity), 0);
+
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert!(
+        ctx.is_enabled(),
+        "popped context should be restored to the state before the push"
+    );
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "inputs held across the transition shouldn't fire"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn pop_disabled() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity = app.world_mut().spawn((Player, Inventory)).id();
+
+    app.update();
+
+    let mut instances = app.world_mut().resource_mut::<ContextInstances>();
+    instances.set_enabled::<Inventory>(entity, false);
+    instances.push_layer::<Inventory>(entity, true);
+
+    app.update();
+
+    let mut instances = app.world_mut().resource_mut::<ContextInstances>();
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert!(ctx.is_enabled());
+
+    assert!(instances.pop_layer(entity));
+
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert!(
+        !ctx.is_enabled(),
+        "context disabled before the push should be disabled after the pop"
+    );
+}
+
+#[test]
+fn multiple_entities() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>()
+        .add_input_context::<Dialog>();
+
+    let entity1 = app.world_mut().spawn((Player, Inventory)).id();
+    let entity2 = app.world_mut().spawn((Player, Dialog)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity1, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    assert_eq!(instances.layers_count(entity1), 1);
+    assert_eq!(instances.layers_count(entity2), 0);
+
+    let ctx = instances.get::<Player>(entity1).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+
+    let ctx = instances.get::<Player>(entity2).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::Fired,
+        "layer shouldn't block contexts of other entities"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Dialog>(entity2, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Cancel::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Inventory>(entity1).unwrap();
+    assert_eq!(
+        ctx.action::<Close>().unwrap().state(),
+        ActionState::Fired,
+        "earlier layer shouldn't be blocked by a later layer of another entity"
+    );
+
+    let ctx = instances.get::<Dialog>(entity2).unwrap();
+    assert_eq!(ctx.action::<Cancel>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn multiple_entities_priority() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity1 = app.world_mut().spawn((Player, Inventory)).id();
+    let entity2 = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut instances = app.world_mut().resource_mut::<ContextInstances>();
+    instances.set_priority::<Player>(entity2, 2);
+    instances.push_layer::<Inventory>(entity1, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(DropItem::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity2).unwrap();
+    assert_eq!(
+        ctx.action::<Attack>().unwrap().state(),
+        ActionState::Fired,
+        "layer shouldn't be evaluated before higher-priority contexts of other entities"
+    );
+
+    let ctx = instances.get::<Inventory>(entity1).unwrap();
+    assert_eq!(
+        ctx.action::<DropItem>().unwrap().state(),
+        ActionState::None,
+        "input should be consumed by the higher-priority context"
+    );
+}
+
+#[test]
+fn pop_multiple_entities() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>();
+
+    let entity1 = app.world_mut().spawn((Player, Inventory)).id();
+    let entity2 = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .push_layer::<Inventory>(entity1, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .pop_layer(entity1);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity1).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+
+    let ctx = instances.get::<Player>(entity2).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::Fired,
+        "popping shouldn't affect inputs of other entities"
+    );
+}
+
+#[test]
+fn nested() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Inventory>()
+        .add_input_context::<Dialog>();
+
+    let entity = app.world_mut().spawn((Player, Inventory, Dialog)).id();
+
+    app.update();
+
+    let mut instances = app.world_mut().resource_mut::<ContextInstances>();
+    instances.push_layer::<Inventory>(entity, true);
+    instances.push_layer::<Dialog>(entity, true);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(DropItem::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    assert_eq!(instances.layers_count(entity), 2);
+
+    let ctx = instances.get::<Dialog>(entity).unwrap();
+    assert_eq!(ctx.action::<Confirm>().unwrap().state(), ActionState::Fired);
+
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert_eq!(ctx.action::<DropItem>().unwrap().state(), ActionState::None);
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .pop_layer(entity);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(DropItem::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(DropItem::KEY);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Inventory>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<DropItem>().unwrap().state(),
+        ActionState::Fired,
+        "previous layer should be restored"
+    );
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "previous layer should still block lower contexts"
+    );
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx.bind::<Attack>().to(DropItem::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Inventory;
+
+impl InputContext for Inventory {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<DropItem>().to(DropItem::KEY);
+        ctx.bind::<Close>().to(Cancel::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Dialog;
+
+impl InputContext for Dialog {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Confirm>().to(DropItem::KEY);
+        ctx.bind::<Cancel>().to(Cancel::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Attack;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct DropItem;
+
+impl DropItem {
+    const KEY: KeyCode = KeyCode::KeyQ;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Confirm;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, consume_input = false)]
+struct Close;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Cancel;
+
+impl Cancel {
+    const KEY: KeyCode = KeyCode::Escape;
+}
@@ -0,0 +1,159 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn override_priority() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Vehicle>()
+        .add_input_context::<Player>();
+
+    let first = app.world_mut().spawn(Vehicle).id();
+    let second = app.world_mut().spawn(Vehicle).id();
+    let player = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_priority::<Vehicle>(second, 2);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Accelerate::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    assert_eq!(instances.priority::<Vehicle>(second), Some(2));
+    assert_eq!(
+        instances.priority::<Vehicle>(first),
+        Some(Vehicle::PRIORITY)
+    );
+
+    let ctx = instances.get::<Vehicle>(second).unwrap();
+    assert_eq!(
+        ctx.action::<Accelerate>().unwrap().state(),
+        ActionState::Fired,
+        "instance with overridden priority should be evaluated before other contexts"
+    );
+
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(ctx.action::<Run>().unwrap().state(), ActionState::None);
+
+    let ctx = instances.get::<Vehicle>(first).unwrap();
+    assert_eq!(
+        ctx.action::<Accelerate>().unwrap().state(),
+        ActionState::None,
+        "other instances of the same type should keep the default priority"
+    );
+}
+
+#[test]
+fn restore_priority() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Vehicle>()
+        .add_input_context::<Player>();
+
+    let vehicle = app.world_mut().spawn(Vehicle).id();
+    let player = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_priority::<Vehicle>(vehicle, 2);
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_priority::<Vehicle>(vehicle, Vehicle::PRIORITY);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Accelerate::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(ctx.action::<Run>().unwrap().state(), ActionState::Fired);
+
+    let ctx = instances.get::<Vehicle>(vehicle).unwrap();
+    assert_eq!(
+        ctx.action::<Accelerate>().unwrap().state(),
+        ActionState::None
+    );
+}
+
+#[test]
+fn ties() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Vehicle>();
+
+    let first = app.world_mut().spawn(Vehicle).id();
+    let second = app.world_mut().spawn(Vehicle).id();
+
+    app.update();
+
+    // Changing the priority back and forth shouldn't affect the order of equal instances.
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_priority::<Vehicle>(first, -1);
+    app.world_mut()
+        .resource_mut::<ContextInstances>()
+        .set_priority::<Vehicle>(first, Vehicle::PRIORITY);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Accelerate::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Vehicle>(first).unwrap();
+    assert_eq!(
+        ctx.action::<Accelerate>().unwrap().state(),
+        ActionState::Fired,
+        "instances with equal priority should be evaluated in the order they were added"
+    );
+
+    let ctx = instances.get::<Vehicle>(second).unwrap();
+    assert_eq!(
+        ctx.action::<Accelerate>().unwrap().state(),
+        ActionState::None
+    );
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Run>().to(Accelerate::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Vehicle;
+
+impl InputContext for Vehicle {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Accelerate>().to(Accelerate::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, consume_input = true)]
+struct Accelerate;
+
+impl Accelerate {
+    const KEY: KeyCode = KeyCode::KeyW;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, consume_input = true)]
+struct Run;
@@ -0,0 +1,155 @@
+use bevy::{input::InputPlugin, prelude::*, window::WindowFocused};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn reset() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Overlay>()
+        .init_resource::<CompletedCount>()
+        .add_observer(record_completed);
+
+    let entity = app.world_mut().spawn((Player, Overlay)).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Jump::KEY);
+    keys.press(Toggle::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+
+    set_focus(&mut app, false);
+
+    app.update();
+
+    assert_eq!(**app.world().resource::<CompletedCount>(), 1);
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+
+    let ctx = instances.get::<Overlay>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Toggle>().unwrap().state(),
+        ActionState::Fired,
+        "context with disabled reset shouldn't be affected"
+    );
+
+    set_focus(&mut app, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "held key should be ignored until released"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn switch_window() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    set_focus(&mut app, false);
+    set_focus(&mut app, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::Fired,
+        "focus moved to another window of the app"
+    );
+}
+
+fn set_focus(app: &mut App, focused: bool) {
+    app.world_mut().send_event(WindowFocused {
+        window: Entity::PLACEHOLDER,
+        focused,
+    });
+}
+
+fn record_completed(_trigger: Trigger<Completed<Jump>>, mut completed: ResMut<CompletedCount>) {
+    **completed += 1;
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct CompletedCount(usize);
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Overlay;
+
+impl InputContext for Overlay {
+    const RESET_ON_FOCUS_LOSS: bool = false;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Toggle>().to(Toggle::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Toggle;
+
+impl Toggle {
+    const KEY: KeyCode = KeyCode::Tab;
+}
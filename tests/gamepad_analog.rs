@@ -0,0 +1,90 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn threshold() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let gamepad_entity = app.world_mut().spawn(Gamepad::default()).id();
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(TRIGGER, 0.3);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Accelerate>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+    assert_eq!(action.value(), 0.3.into());
+    assert_eq!(
+        ctx.action::<Fire>().unwrap().state(),
+        ActionState::None,
+        "trigger should be pressed past the threshold"
+    );
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(TRIGGER, 0.8);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Accelerate>().unwrap().value(), 0.8.into());
+    assert_eq!(ctx.action::<Fire>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn held_below_threshold() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let mut gamepad = Gamepad::default();
+    gamepad.analog_mut().set(TRIGGER, 0.3);
+    let gamepad_entity = app.world_mut().spawn(gamepad).id();
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(TRIGGER, 0.8);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Fire>().unwrap().state(),
+        ActionState::Fired,
+        "trigger held below the threshold should be considered released"
+    );
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Accelerate>().to(Input::gamepad_analog(TRIGGER));
+        ctx.bind::<Fire>()
+            .to(Input::gamepad_analog(TRIGGER).with_actuation(0.5));
+        ctx
+    }
+}
+
+const TRIGGER: GamepadButton = GamepadButton::RightTrigger2;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = f32, consume_input = false)]
+struct Accelerate;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Fire;
@@ -0,0 +1,321 @@
+use bevy::{
+    input::{
+        gamepad::{
+            GamepadConnection, GamepadConnectionEvent, RawGamepadButtonChangedEvent,
+            RawGamepadEvent,
+        },
+        InputPlugin,
+    },
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn on_connect() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .init_resource::<AssignmentEvents>()
+        .add_observer(record_joined);
+
+    let player1 = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+    let player2 = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+
+    app.update();
+
+    let gamepad_entity = connect(&mut app);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player1).unwrap().gamepad(),
+        Some(gamepad_entity)
+    );
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player2).unwrap().gamepad(),
+        None
+    );
+    assert_eq!(
+        app.world().resource::<AssignmentEvents>().joined,
+        [(player1, gamepad_entity)]
+    );
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player1).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+
+    let ctx = instances.get::<Player>(player2).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "slot without a gamepad shouldn't read any gamepad"
+    );
+}
+
+#[test]
+fn on_press() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(GamepadAssignment {
+            join: GamepadJoin::OnPress(GamepadButton::Start),
+            ..Default::default()
+        })
+        .add_input_context::<Player>();
+
+    let player = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+
+    let gamepad_entity = connect(&mut app);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player).unwrap().gamepad(),
+        None
+    );
+
+    app.world_mut()
+        .send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad_entity,
+            GamepadButton::Start,
+            1.0,
+        )));
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player).unwrap().gamepad(),
+        Some(gamepad_entity)
+    );
+}
+
+#[test]
+fn pause() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .init_resource::<AssignmentEvents>()
+        .add_observer(record_joined)
+        .add_observer(record_left);
+
+    let player = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+
+    let gamepad_entity1 = connect(&mut app);
+
+    app.update();
+
+    disconnect(&mut app, gamepad_entity1);
+    let gamepad_entity2 = connect(&mut app);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player).unwrap().gamepad(),
+        Some(gamepad_entity1),
+        "slot should be reserved for the disconnected gamepad"
+    );
+
+    let mut gamepad2 = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad2.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+
+    reconnect(&mut app, gamepad_entity1);
+
+    app.update();
+
+    let events = app.world().resource::<AssignmentEvents>();
+    assert_eq!(events.left, [(player, gamepad_entity1)]);
+    assert_eq!(
+        events.joined,
+        [(player, gamepad_entity1), (player, gamepad_entity1)]
+    );
+}
+
+#[test]
+fn fallback() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(GamepadAssignment {
+            disconnect: GamepadDisconnect::Fallback,
+            ..Default::default()
+        })
+        .add_input_context::<Player>()
+        .init_resource::<AssignmentEvents>()
+        .add_observer(record_reassigned);
+
+    let player = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+
+    let gamepad_entity1 = connect(&mut app);
+    let gamepad_entity2 = connect(&mut app);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player).unwrap().gamepad(),
+        Some(gamepad_entity1)
+    );
+
+    disconnect(&mut app, gamepad_entity1);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player).unwrap().gamepad(),
+        Some(gamepad_entity2)
+    );
+    assert_eq!(
+        app.world().resource::<AssignmentEvents>().reassigned,
+        [(player, gamepad_entity1, gamepad_entity2)]
+    );
+
+    let mut gamepad2 = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad2.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn spawn_order() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    // Make the first player reuse a despawned index, so its entity is greater than the second's.
+    let reused = app.world_mut().spawn_empty().id();
+    app.world_mut().despawn(reused);
+    let player1 = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+    let player2 = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+    assert!(player1 > player2);
+
+    app.update();
+
+    let gamepad_entity = connect(&mut app);
+
+    app.update();
+
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player1).unwrap().gamepad(),
+        Some(gamepad_entity),
+        "free slots should be filled in the spawn order"
+    );
+    assert_eq!(
+        app.world().get::<GamepadSlot>(player2).unwrap().gamepad(),
+        None
+    );
+}
+
+#[test]
+fn rebuild() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let player = app.world_mut().spawn((Player, GamepadSlot::default())).id();
+    let gamepad_entity1 = connect(&mut app);
+
+    app.update();
+
+    let gamepad_entity2 = app.world_mut().spawn(Gamepad::default()).id();
+    app.world_mut().trigger(RebuildInputContexts);
+
+    app.update();
+
+    let mut gamepad2 = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad2.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "assigned gamepad should be kept after rebuild"
+    );
+
+    let mut gamepad1 = app.world_mut().get_mut::<Gamepad>(gamepad_entity1).unwrap();
+    gamepad1.digital_mut().press(Jump::BUTTON);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(player).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+fn connect(app: &mut App) -> Entity {
+    let gamepad_entity = app.world_mut().spawn_empty().id();
+    reconnect(app, gamepad_entity);
+    gamepad_entity
+}
+
+fn reconnect(app: &mut App, gamepad_entity: Entity) {
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad_entity,
+        GamepadConnection::Connected {
+            name: "Dummy".to_string(),
+            vendor_id: None,
+            product_id: None,
+        },
+    ));
+}
+
+fn disconnect(app: &mut App, gamepad_entity: Entity) {
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad_entity,
+        GamepadConnection::Disconnected,
+    ));
+}
+
+fn record_joined(trigger: Trigger<GamepadJoined>, mut events: ResMut<AssignmentEvents>) {
+    events.joined.push((trigger.entity(), trigger.gamepad));
+}
+
+fn record_left(trigger: Trigger<GamepadLeft>, mut events: ResMut<AssignmentEvents>) {
+    events.left.push((trigger.entity(), trigger.gamepad));
+}
+
+fn record_reassigned(trigger: Trigger<GamepadReassigned>, mut events: ResMut<AssignmentEvents>) {
+    events
+        .reassigned
+        .push((trigger.entity(), trigger.from, trigger.to));
+}
+
+#[derive(Resource, Default)]
+struct AssignmentEvents {
+    joined: Vec<(Entity, Entity)>,
+    left: Vec<(Entity, Entity)>,
+    reassigned: Vec<(Entity, Entity, Entity)>,
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::BUTTON);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const BUTTON: GamepadButton = GamepadButton::South;
+}
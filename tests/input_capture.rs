@@ -0,0 +1,218 @@
+use bevy::{
+    input::{
+        gamepad::{RawGamepadButtonChangedEvent, RawGamepadEvent},
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState, InputPlugin,
+    },
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn keyboard_with_mod_keys() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Captured>()
+        .add_observer(store_captured)
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputCapture>()
+        .start(CaptureSettings::default());
+
+    // Send events because `InputPlugin` clears just pressed buttons.
+    send_key(&mut app, KeyCode::ControlLeft, ButtonState::Pressed);
+    send_key(&mut app, Jump::KEY, ButtonState::Pressed);
+
+    app.update();
+
+    let captured = app.world().resource::<Captured>();
+    assert_eq!(
+        captured.0,
+        Some(Some(Jump::KEY.with_mod_keys(ModKeys::CONTROL)))
+    );
+    assert!(!app.world().resource::<InputCapture>().is_active());
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(
+        action.state(),
+        ActionState::None,
+        "actions shouldn't be evaluated during capture"
+    );
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(
+        action.state(),
+        ActionState::None,
+        "captured input should be ignored until released"
+    );
+}
+
+#[test]
+fn mod_key() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Captured>()
+        .add_observer(store_captured);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputCapture>()
+        .start(CaptureSettings::default());
+
+    send_key(&mut app, KeyCode::ShiftLeft, ButtonState::Pressed);
+
+    app.update();
+
+    assert!(
+        app.world().resource::<Captured>().0.is_none(),
+        "modifiers should be captured only on release"
+    );
+
+    send_key(&mut app, KeyCode::ShiftLeft, ButtonState::Released);
+
+    app.update();
+
+    let captured = app.world().resource::<Captured>();
+    assert_eq!(captured.0, Some(Some(KeyCode::ShiftLeft.into())));
+}
+
+#[test]
+fn filters() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Captured>()
+        .add_observer(store_captured);
+
+    let gamepad_entity = app.world_mut().spawn(Gamepad::default()).id();
+
+    app.update();
+
+    app.world_mut().resource_mut::<InputCapture>().start(
+        CaptureSettings::default()
+            .with_devices(CaptureDevices::GAMEPAD)
+            .with_excluded(GamepadButton::Start)
+            .with_cancel(GamepadButton::Select)
+            .with_actuation(0.5),
+    );
+
+    send_key(&mut app, KeyCode::KeyA, ButtonState::Pressed);
+
+    press_gamepad_button(&mut app, gamepad_entity, GamepadButton::Start);
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.2);
+
+    app.update();
+
+    assert!(
+        app.world().resource::<Captured>().0.is_none(),
+        "excluded inputs, other devices and small axis values shouldn't be captured"
+    );
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.7);
+
+    app.update();
+
+    let captured = app.world().resource::<Captured>();
+    assert_eq!(captured.0, Some(Some(GamepadAxis::LeftStickX.into())));
+
+    app.world_mut().resource_mut::<Captured>().0 = None;
+    app.world_mut()
+        .resource_mut::<InputCapture>()
+        .start(CaptureSettings::default().with_cancel(GamepadButton::Select));
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.0);
+
+    press_gamepad_button(&mut app, gamepad_entity, GamepadButton::Select);
+
+    app.update();
+
+    let captured = app.world().resource::<Captured>();
+    assert_eq!(captured.0, Some(None), "capture should be canceled");
+}
+
+#[test]
+fn cancel_with_mod_keys() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Captured>()
+        .add_observer(store_captured);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputCapture>()
+        .start(CaptureSettings::default().with_cancel(KeyCode::Escape));
+
+    send_key(&mut app, KeyCode::ShiftLeft, ButtonState::Pressed);
+    send_key(&mut app, KeyCode::Escape, ButtonState::Pressed);
+
+    app.update();
+
+    let captured = app.world().resource::<Captured>();
+    assert_eq!(
+        captured.0,
+        Some(None),
+        "held modifiers shouldn't prevent cancellation"
+    );
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+fn press_gamepad_button(app: &mut App, gamepad_entity: Entity, button: GamepadButton) {
+    app.world_mut()
+        .send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad_entity,
+            button,
+            1.0,
+        )));
+}
+
+fn store_captured(trigger: Trigger<InputCaptured>, mut captured: ResMut<Captured>) {
+    captured.0 = Some(trigger.input);
+}
+
+/// Result of the last capture.
+#[derive(Resource, Default)]
+struct Captured(Option<Option<Input>>);
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
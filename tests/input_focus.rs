@@ -0,0 +1,177 @@
+#![cfg(all(feature = "ui_priority", not(feature = "picking")))]
+
+use bevy::{input::InputPlugin, prelude::*, ui::FocusPolicy};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn pointer() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let node = app
+        .world_mut()
+        .spawn((Node::default(), Interaction::Hovered, FocusPolicy::Block))
+        .id();
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(Shoot::BUTTON);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let focus = app.world().resource::<InputFocus>();
+    assert_eq!(focus.pointer(), FocusOwner::Ui(node));
+    assert_eq!(focus.keyboard(), FocusOwner::World);
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Shoot>().unwrap().state(), ActionState::None);
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::Fired,
+        "keyboard shouldn't be blocked by hovering"
+    );
+}
+
+#[test]
+fn pass() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    app.world_mut()
+        .spawn((Node::default(), Interaction::Hovered));
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(Shoot::BUTTON);
+
+    app.update();
+
+    let focus = app.world().resource::<InputFocus>();
+    assert_eq!(focus.pointer(), FocusOwner::World);
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Shoot>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn keyboard() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>()
+        .add_input_context::<Menu>();
+
+    let node = app.world_mut().spawn(Interaction::None).id();
+    let entity = app.world_mut().spawn((Player, Menu)).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputFocus>()
+        .set_keyboard_focus(Some(node));
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Jump::KEY);
+    keys.press(Pause::KEY);
+    keys.press(Back::KEY);
+
+    app.update();
+
+    let focus = app.world().resource::<InputFocus>();
+    assert_eq!(focus.keyboard(), FocusOwner::Ui(node));
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+    assert_eq!(
+        ctx.action::<Pause>().unwrap().state(),
+        ActionState::Fired,
+        "action should opt out of UI focus"
+    );
+
+    let ctx = instances.get::<Menu>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Back>().unwrap().state(),
+        ActionState::Fired,
+        "context should opt out of UI focus"
+    );
+
+    app.world_mut()
+        .resource_mut::<InputFocus>()
+        .set_keyboard_focus(None);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Shoot>().to(Shoot::BUTTON);
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx.bind::<Pause>().to(Pause::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Menu;
+
+impl InputContext for Menu {
+    const IGNORE_UI_FOCUS: bool = true;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Back>().to(Back::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Shoot;
+
+impl Shoot {
+    const BUTTON: MouseButton = MouseButton::Left;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, ignore_ui_focus = true)]
+struct Pause;
+
+impl Pause {
+    const KEY: KeyCode = KeyCode::Escape;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Back;
+
+impl Back {
+    const KEY: KeyCode = KeyCode::Backspace;
+}
@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use bevy::{input::InputPlugin, prelude::*, time::TimeUpdateStrategy};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn playback() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputRecorder>()
+        .start_recording();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    let mut recorded_states = Vec::new();
+    for _ in 0..FRAMES {
+        app.update();
+        recorded_states.push(jump_state(&app, entity));
+    }
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+    recorded_states.push(jump_state(&app, entity));
+
+    assert!(recorded_states.contains(&ActionState::Fired));
+
+    let recording = app
+        .world_mut()
+        .resource_mut::<InputRecorder>()
+        .stop_recording()
+        .expect("recording should be started");
+    assert_eq!(recording.frames.len(), FRAMES + 1);
+
+    let serialized = ron::to_string(&recording).unwrap();
+    let deserialized: InputRecording = ron::from_str(&serialized).unwrap();
+    assert_eq!(recording, deserialized);
+
+    // Use a different time step to ensure that recorded time is used.
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<InputRecorder>()
+        .start_playback(deserialized);
+
+    let mut replayed_states = Vec::new();
+    for _ in 0..=FRAMES {
+        app.update();
+        replayed_states.push(jump_state(&app, entity));
+    }
+
+    assert_eq!(recorded_states, replayed_states);
+
+    app.update();
+
+    let recorder = app.world().resource::<InputRecorder>();
+    assert!(
+        !recorder.is_playing(),
+        "playback should stop after the last frame"
+    );
+}
+
+fn jump_state(app: &App, entity: Entity) -> ActionState {
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    ctx.action::<Jump>().unwrap().state()
+}
+
+const FRAMES: usize = 5;
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>()
+            .to(Jump::KEY)
+            .with_conditions(Hold::new(0.3));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
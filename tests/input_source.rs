@@ -0,0 +1,124 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn custom() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<RemoteState>()
+        .add_input_source(RemoteController::default())
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut().resource_mut::<RemoteState>().jump_pressed = true;
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+
+    app.world_mut().resource_mut::<RemoteState>().jump_pressed = false;
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::None);
+}
+
+#[test]
+fn consume() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<RemoteState>()
+        .add_input_source(RemoteController::default())
+        .add_input_context::<Player>()
+        .add_input_context::<Vehicle>();
+
+    let entity = app.world_mut().spawn((Player, Vehicle)).id();
+
+    app.update();
+
+    app.world_mut().resource_mut::<RemoteState>().jump_pressed = true;
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Vehicle>(entity).unwrap();
+    let action = ctx.action::<Brake>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(
+        action.state(),
+        ActionState::None,
+        "input should be consumed by `Brake`"
+    );
+}
+
+#[derive(Resource, Default)]
+struct RemoteState {
+    jump_pressed: bool,
+}
+
+#[derive(Default)]
+struct RemoteController {
+    jump_pressed: bool,
+}
+
+impl RemoteController {
+    const JUMP: u32 = 0;
+}
+
+impl InputSource for RemoteController {
+    fn update(&mut self, world: &World) {
+        self.jump_pressed = world.resource::<RemoteState>().jump_pressed;
+    }
+
+    fn value(&self, id: u32) -> Option<ActionValue> {
+        match id {
+            Self::JUMP => Some(self.jump_pressed.into()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Input::Custom(RemoteController::JUMP));
+        ctx
+    }
+}
+
+#[derive(Debug, Component)]
+struct Vehicle;
+
+impl InputContext for Vehicle {
+    const PRIORITY: isize = 1;
+
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Brake>()
+            .to(Input::Custom(RemoteController::JUMP));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Brake;
@@ -0,0 +1,133 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState, InputPlugin,
+    },
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn global() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Changes>()
+        .add_observer(store_change);
+
+    let gamepad_entity = app.world_mut().spawn(Gamepad::default()).id();
+
+    app.update();
+
+    assert_eq!(app.world().resource::<LastInputDevice>().get(), None);
+
+    // Send events because `InputPlugin` clears just pressed buttons.
+    app.world_mut().send_event(KeyboardInput {
+        key_code: KeyCode::KeyA,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state: ButtonState::Pressed,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<LastInputDevice>().get(),
+        Some(InputDevice::Keyboard)
+    );
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.1);
+
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<LastInputDevice>().get(),
+        Some(InputDevice::Keyboard),
+        "stick drift shouldn't change the device"
+    );
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickX, 1.0);
+
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<LastInputDevice>().get(),
+        Some(InputDevice::Gamepad(gamepad_entity))
+    );
+
+    let changes = app.world().resource::<Changes>();
+    assert_eq!(
+        changes.0,
+        [
+            (Entity::PLACEHOLDER, InputDevice::Keyboard),
+            (Entity::PLACEHOLDER, InputDevice::Gamepad(gamepad_entity))
+        ]
+    );
+}
+
+#[test]
+fn per_context() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<Changes>()
+        .add_observer(store_change)
+        .add_input_context::<Player>();
+
+    let gamepad_entity1 = app.world_mut().spawn(Gamepad::default()).id();
+    let gamepad_entity2 = app.world_mut().spawn(Gamepad::default()).id();
+    let player_entity1 = app.world_mut().spawn(Player(gamepad_entity1)).id();
+    let player_entity2 = app.world_mut().spawn(Player(gamepad_entity2)).id();
+
+    app.update();
+
+    let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad_entity2).unwrap();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickY, -1.0);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx1 = instances.get::<Player>(player_entity1).unwrap();
+    assert_eq!(ctx1.last_device(), None);
+    let ctx2 = instances.get::<Player>(player_entity2).unwrap();
+    assert_eq!(
+        ctx2.last_device(),
+        Some(InputDevice::Gamepad(gamepad_entity2))
+    );
+
+    let changes = app.world().resource::<Changes>();
+    assert_eq!(
+        changes.0,
+        [
+            (Entity::PLACEHOLDER, InputDevice::Gamepad(gamepad_entity2)),
+            (player_entity2, InputDevice::Gamepad(gamepad_entity2)),
+        ]
+    );
+}
+
+fn store_change(trigger: Trigger<InputDeviceChanged>, mut changes: ResMut<Changes>) {
+    changes.0.push((trigger.entity(), trigger.device));
+}
+
+/// Triggered changes with their targets.
+#[derive(Resource, Default)]
+struct Changes(Vec<(Entity, InputDevice)>);
+
+#[derive(Component)]
+struct Player(Entity);
+
+impl InputContext for Player {
+    fn context_instance(world: &World, entity: Entity) -> ContextInstance {
+        let gamepad_entity = world.get::<Player>(entity).unwrap().0;
+
+        let mut ctx = ContextInstance::default();
+        ctx.set_gamepad(gamepad_entity);
+        ctx.bind::<Move>().to(GamepadStick::Left);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct Move;
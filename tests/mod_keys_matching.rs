@@ -0,0 +1,151 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn subset() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn(Editor).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ControlLeft);
+    keys.press(Subset::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Subset>().unwrap().state(),
+        ActionState::Fired,
+        "extra modifiers should be ignored"
+    );
+}
+
+#[test]
+fn exact() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn(Editor).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ControlLeft);
+    keys.press(Exact::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Exact>().unwrap().state(),
+        ActionState::None,
+        "extra modifiers should prevent activation"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(KeyCode::ControlLeft);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(ctx.action::<Exact>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn sided() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Editor>();
+
+    let entity = app.world_mut().spawn(Editor).id();
+
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(KeyCode::ShiftRight);
+    keys.press(Sided::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Sided>().unwrap().state(),
+        ActionState::None,
+        "only left modifier should activate"
+    );
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(KeyCode::ShiftRight);
+    keys.press(KeyCode::ShiftLeft);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(ctx.action::<Sided>().unwrap().state(), ActionState::Fired);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ShiftRight);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Editor>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Sided>().unwrap().state(),
+        ActionState::None,
+        "right modifier should be extra for the exact matching"
+    );
+}
+
+#[derive(Debug, Component)]
+struct Editor;
+
+impl InputContext for Editor {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Subset>().to(Subset::KEY);
+        ctx.bind::<Exact>()
+            .to(Exact::KEY.with_mod_keys_matching(ModKeysMatching::Exact));
+        ctx.bind::<Sided>().to(Sided::KEY
+            .with_mod_keys(ModKeys::SHIFT_LEFT)
+            .with_mod_keys_matching(ModKeysMatching::Exact));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Subset;
+
+impl Subset {
+    const KEY: KeyCode = KeyCode::KeyA;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Exact;
+
+impl Exact {
+    const KEY: KeyCode = KeyCode::KeyS;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Sided;
+
+impl Sided {
+    const KEY: KeyCode = KeyCode::KeyD;
+}
@@ -0,0 +1,103 @@
+use bevy::{
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        InputPlugin,
+    },
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn wheel_steps() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut().send_event(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 2.0,
+        window: Entity::PLACEHOLDER,
+    });
+
+    for frame in 0..2 {
+        app.update();
+
+        let instances = app.world().resource::<ContextInstances>();
+        let ctx = instances.get::<Player>(entity).unwrap();
+        assert_eq!(
+            ctx.action::<NextWeapon>().unwrap().state(),
+            ActionState::Fired,
+            "action should fire once per notch, but didn't on frame {frame}"
+        );
+        assert_eq!(
+            ctx.action::<PrevWeapon>().unwrap().state(),
+            ActionState::None
+        );
+    }
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<NextWeapon>().unwrap().state(),
+        ActionState::None
+    );
+}
+
+#[test]
+fn motion() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut().send_event(MouseMotion {
+        delta: Vec2::new(-5.0, 1.0),
+    });
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<LookLeft>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+    assert_eq!(action.value(), 5.0.into());
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<NextWeapon>()
+            .to(Input::mouse_wheel_direction(MouseDirection::Up).with_modifiers(Steps::default()));
+        ctx.bind::<PrevWeapon>().to(
+            Input::mouse_wheel_direction(MouseDirection::Down).with_modifiers(Steps::default()),
+        );
+        ctx.bind::<LookLeft>()
+            .to(Input::mouse_motion_direction(MouseDirection::Left));
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct NextWeapon;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct PrevWeapon;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+struct LookLeft;
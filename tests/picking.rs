@@ -0,0 +1,172 @@
+#![cfg(feature = "picking")]
+
+use bevy::{
+    input::InputPlugin,
+    picking::{backend::HitData, focus::HoverMap, pointer::PointerId, PickingBehavior},
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn entity_press() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<HoverMap>()
+        .add_entity_filter::<With<Unit>>(Unit::FILTER)
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+    let unit = app.world_mut().spawn(Unit).id();
+    let ground = app.world_mut().spawn_empty().id();
+
+    app.update();
+
+    hover(&mut app, &[(ground, 1.0)]);
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(MouseButton::Left);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Select>().unwrap().state(),
+        ActionState::None,
+        "entity without `Unit` shouldn't be pressed"
+    );
+
+    let mut mouse_buttons = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+    mouse_buttons.release(MouseButton::Left);
+    mouse_buttons.clear();
+
+    app.update();
+
+    hover(&mut app, &[(ground, 2.0), (unit, 1.0)]);
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(MouseButton::Left);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Select>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+
+    let pressed = app.world().resource::<PressedEntities>();
+    assert_eq!(pressed.entity(action.input().unwrap()), Some(unit));
+
+    hover(&mut app, &[(ground, 1.0)]);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Select>().unwrap().state(),
+        ActionState::Fired,
+        "press should be held until release"
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .release(MouseButton::Left);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Select>().unwrap().state(), ActionState::None);
+
+    let pressed = app.world().resource::<PressedEntities>();
+    assert_eq!(
+        pressed.entity(Input::entity_press(Unit::FILTER)),
+        Some(unit),
+        "entity should be preserved after release"
+    );
+}
+
+#[test]
+fn ui_pointer() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .init_resource::<HoverMap>()
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+    let node = app.world_mut().spawn(Node::default()).id();
+
+    app.update();
+
+    hover(&mut app, &[(node, 0.0)]);
+    app.world_mut()
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(Shoot::BUTTON);
+
+    app.update();
+
+    let focus = app.world().resource::<InputFocus>();
+    assert_eq!(focus.pointer(), FocusOwner::Ui(node));
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Shoot>().unwrap().state(), ActionState::None);
+
+    app.world_mut().entity_mut(node).insert(PickingBehavior {
+        should_block_lower: false,
+        is_hoverable: true,
+    });
+
+    app.update();
+
+    let focus = app.world().resource::<InputFocus>();
+    assert_eq!(focus.pointer(), FocusOwner::World);
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Shoot>().unwrap().state(), ActionState::Fired);
+}
+
+/// Replaces entities hovered by the mouse with the specified depths.
+fn hover(app: &mut App, entities: &[(Entity, f32)]) {
+    let hovered = entities
+        .iter()
+        .map(|&(entity, depth)| (entity, HitData::new(Entity::PLACEHOLDER, depth, None, None)))
+        .collect();
+
+    app.world_mut()
+        .resource_mut::<HoverMap>()
+        .insert(PointerId::Mouse, hovered);
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Select>().to(Input::entity_press(Unit::FILTER));
+        ctx.bind::<Shoot>().to(Shoot::BUTTON);
+        ctx
+    }
+}
+
+#[derive(Component)]
+struct Unit;
+
+impl Unit {
+    const FILTER: u32 = 0;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, consume_input = false)]
+struct Select;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Shoot;
+
+impl Shoot {
+    const BUTTON: MouseButton = MouseButton::Left;
+}
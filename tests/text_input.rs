@@ -0,0 +1,131 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn keyboard() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<TextInputMode>()
+        .set_enabled(true);
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.press(Jump::KEY);
+    keys.press(SendMessage::KEY);
+
+    app.update();
+
+    assert!(app.world().resource::<TextInputMode>().is_active());
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::None);
+    assert_eq!(
+        ctx.action::<SendMessage>().unwrap().state(),
+        ActionState::Fired,
+        "action should stay active during text input"
+    );
+
+    app.world_mut()
+        .resource_mut::<TextInputMode>()
+        .set_enabled(false);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(
+        ctx.action::<Jump>().unwrap().state(),
+        ActionState::None,
+        "held keys should be ignored after text input"
+    );
+    assert_eq!(
+        ctx.action::<SendMessage>().unwrap().state(),
+        ActionState::Fired
+    );
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(Jump::KEY);
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    assert_eq!(ctx.action::<Jump>().unwrap().state(), ActionState::Fired);
+}
+
+#[test]
+fn ime() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin));
+
+    let window = app
+        .world_mut()
+        .spawn(Window {
+            focused: true,
+            ime_enabled: true,
+            ..Default::default()
+        })
+        .id();
+
+    app.update();
+
+    assert!(app.world().resource::<TextInputMode>().is_active());
+
+    app.world_mut()
+        .resource_mut::<TextInputMode>()
+        .set_detect_ime(false);
+
+    app.update();
+
+    assert!(!app.world().resource::<TextInputMode>().is_active());
+
+    let mut text_input = app.world_mut().resource_mut::<TextInputMode>();
+    text_input.set_detect_ime(true);
+
+    app.world_mut().get_mut::<Window>(window).unwrap().focused = false;
+
+    app.update();
+
+    assert!(!app.world().resource::<TextInputMode>().is_active());
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<Jump>().to(Jump::KEY);
+        ctx.bind::<SendMessage>().to(SendMessage::KEY);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, active_in_text_input = true)]
+struct SendMessage;
+
+impl SendMessage {
+    const KEY: KeyCode = KeyCode::Enter;
+}
@@ -0,0 +1,102 @@
+use bevy::{input::InputPlugin, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+#[test]
+fn keyboard() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let entity = app.world_mut().spawn(Player).id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<VirtualInput>()
+        .insert(Jump::KEY, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+
+    app.world_mut()
+        .resource_mut::<VirtualInput>()
+        .remove(Jump::KEY);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::None);
+}
+
+#[test]
+fn gamepad() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, EnhancedInputPlugin))
+        .add_input_context::<Player>();
+
+    let gamepad_entity1 = app.world_mut().spawn_empty().id();
+    let gamepad_entity2 = app.world_mut().spawn_empty().id();
+    let entity1 = app
+        .world_mut()
+        .spawn((Player, AiGamepad(gamepad_entity1)))
+        .id();
+    let entity2 = app
+        .world_mut()
+        .spawn((Player, AiGamepad(gamepad_entity2)))
+        .id();
+
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<VirtualInput>()
+        .insert_gamepad(gamepad_entity1, Jump::BUTTON, true);
+
+    app.update();
+
+    let instances = app.world().resource::<ContextInstances>();
+    let ctx = instances.get::<Player>(entity1).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(action.state(), ActionState::Fired);
+
+    let ctx = instances.get::<Player>(entity2).unwrap();
+    let action = ctx.action::<Jump>().unwrap();
+    assert_eq!(
+        action.state(),
+        ActionState::None,
+        "only `{gamepad_entity1:?}` should have injected input"
+    );
+}
+
+#[derive(Debug, Component)]
+struct Player;
+
+impl InputContext for Player {
+    fn context_instance(world: &World, entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+
+        if let Some(gamepad) = world.get::<AiGamepad>(entity) {
+            ctx.set_gamepad(**gamepad);
+        }
+        ctx.bind::<Jump>().to((Jump::KEY, Jump::BUTTON));
+
+        ctx
+    }
+}
+
+#[derive(Component, Deref)]
+struct AiGamepad(Entity);
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct Jump;
+
+impl Jump {
+    const KEY: KeyCode = KeyCode::Space;
+    const BUTTON: GamepadButton = GamepadButton::South;
+}